@@ -1,20 +1,26 @@
 use crate::structs::Transmuter;
-use crate::VaultAuth;
+use crate::{TransmuterError, VaultAuth, RESOLVE_TIP_LAMPORTS, ROLE_RESOLVE};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{close_account, CloseAccount, Mint, Token, TokenAccount};
 
 #[derive(Accounts)]
 #[instruction(seed: u64, vault_seed: u64)]
 pub struct CreatorBurnInput<'info> {
-    #[account(mut, constraint = *creator.to_account_info().key == transmuter.creator)]
-    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        constraint = !transmuter.creator_resolve_only || transmuter.has_role(cranker.key, ROLE_RESOLVE) @ TransmuterError::CreatorResolveOnly,
+    )]
+    pub cranker: Signer<'info>,
+    #[account(mut, constraint = *creator.to_account_info().key == transmuter.authority)]
+    pub creator: SystemAccount<'info>,
     #[account(mut, constraint = *user.to_account_info().key == vault_auth.user)]
     pub user: SystemAccount<'info>,
     #[account(mut)]
     pub mint: Box<Account<'info, Mint>>,
     #[account(
         mut,
-        seeds = [b"transmuter", creator.key.as_ref(), seed.to_le_bytes().as_ref()],
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
         bump = transmuter.transmuter_bump,
     )]
     pub transmuter: Box<Account<'info, Transmuter>>,
@@ -24,7 +30,50 @@ pub struct CreatorBurnInput<'info> {
         bump = vault_auth.vault_auth_bump,
     )]
     pub vault_auth: Box<Account<'info, VaultAuth>>,
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = vault.key() == get_associated_token_address(&vault_auth.key(), &mint.key())
+            && vault.owner == vault_auth.key() @ TransmuterError::InvalidVault,
+    )]
     pub vault: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
 }
+
+impl<'info> CreatorBurnInput<'info> {
+    //Vault rent tips the cranker, the rest goes back to the user with the vault auth
+    pub fn close_vault(&self, vault_seed: u64) -> Result<()> {
+        let vault_seed_bytes = vault_seed.to_le_bytes();
+        let seeds = &[
+            b"vaultAuth",
+            self.transmuter.to_account_info().key.as_ref(),
+            self.user.to_account_info().key.as_ref(),
+            vault_seed_bytes.as_ref(),
+            &[self.vault_auth.vault_auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let vault_rent = self.vault.to_account_info().lamports();
+        let cpi_accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.vault_auth.to_account_info(),
+            authority: self.vault_auth.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+
+        close_account(CpiContext::new_with_signer(
+            cpi_program,
+            cpi_accounts,
+            signer_seeds,
+        ))?;
+
+        let tip = vault_rent.min(RESOLVE_TIP_LAMPORTS);
+        **self
+            .vault_auth
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= tip;
+        **self.cranker.to_account_info().try_borrow_mut_lamports()? += tip;
+
+        Ok(())
+    }
+}
@@ -1,22 +1,33 @@
-use crate::VaultAuth;
 use crate::{structs::Transmuter, TransmuterError};
+use crate::{VaultAuth, RESOLVE_TIP_LAMPORTS, ROLE_RESOLVE};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{
+    close_account, transfer, CloseAccount, Mint, Token, TokenAccount, Transfer,
+};
 
 #[derive(Accounts)]
 #[instruction(seed: u64, vault_seed: u64)]
 pub struct CreatorResolveInput<'info> {
-    #[account(mut, constraint = *creator.to_account_info().key == transmuter.creator)]
-    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        constraint = !transmuter.creator_resolve_only || transmuter.has_role(cranker.key, ROLE_RESOLVE) @ TransmuterError::CreatorResolveOnly,
+    )]
+    pub cranker: Signer<'info>,
+    #[account(mut, constraint = *creator.to_account_info().key == transmuter.authority)]
+    pub creator: SystemAccount<'info>,
     #[account(mut, constraint = *user.to_account_info().key == vault_auth.user)]
     pub user: SystemAccount<'info>,
     #[account(mut)]
     pub mint: Box<Account<'info, Mint>>,
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = ata.owner == creator.key() && ata.mint == mint.key() @ TransmuterError::InvalidCreatorAta,
+    )]
     pub ata: Account<'info, TokenAccount>,
     #[account(
         mut,
-        seeds = [b"transmuter", creator.key.as_ref(), seed.to_le_bytes().as_ref()],
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
         bump = transmuter.transmuter_bump,
     )]
     pub transmuter: Box<Account<'info, Transmuter>>,
@@ -26,7 +37,11 @@ pub struct CreatorResolveInput<'info> {
         bump = vault_auth.vault_auth_bump,
     )]
     pub vault_auth: Box<Account<'info, VaultAuth>>,
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = vault.key() == get_associated_token_address(&vault_auth.key(), &mint.key())
+            && vault.owner == vault_auth.key() @ TransmuterError::InvalidVault,
+    )]
     pub vault: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
 }
@@ -56,4 +71,41 @@ impl<'info> CreatorResolveInput<'info> {
             1,
         )
     }
+
+    //Vault rent tips the cranker, the rest goes back to the user with the vault auth
+    pub fn close_vault(&self, vault_seed: u64) -> Result<()> {
+        let vault_seed_bytes = vault_seed.to_le_bytes();
+        let seeds = &[
+            b"vaultAuth",
+            self.transmuter.to_account_info().key.as_ref(),
+            self.user.to_account_info().key.as_ref(),
+            vault_seed_bytes.as_ref(),
+            &[self.vault_auth.vault_auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let vault_rent = self.vault.to_account_info().lamports();
+        let cpi_accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.vault_auth.to_account_info(),
+            authority: self.vault_auth.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+
+        close_account(CpiContext::new_with_signer(
+            cpi_program,
+            cpi_accounts,
+            signer_seeds,
+        ))?;
+
+        let tip = vault_rent.min(RESOLVE_TIP_LAMPORTS);
+        **self
+            .vault_auth
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= tip;
+        **self.cranker.to_account_info().try_borrow_mut_lamports()? += tip;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,140 @@
+use crate::structs::Transmuter;
+use crate::{pay_resolve_tip, LeafInfo, TransmuterError, VaultAuth, ROLE_RESOLVE};
+use anchor_lang::prelude::*;
+use mpl_bubblegum::instructions::{BurnCpiBuilder, TransferCpiBuilder};
+use mpl_bubblegum::programs::{SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID};
+
+#[derive(Accounts)]
+#[instruction(seed: u64, vault_seed: u64)]
+pub struct CreatorResolveInputCnft<'info> {
+    #[account(
+        mut,
+        constraint = !transmuter.creator_resolve_only || transmuter.has_role(cranker.key, ROLE_RESOLVE) @ TransmuterError::CreatorResolveOnly,
+    )]
+    pub cranker: Signer<'info>,
+    #[account(mut, constraint = *creator.to_account_info().key == transmuter.authority)]
+    pub creator: SystemAccount<'info>,
+    #[account(mut, constraint = *user.to_account_info().key == vault_auth.user)]
+    pub user: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        mut,
+        seeds = [b"vaultAuth", transmuter.key().as_ref(), user.key.as_ref(), vault_seed.to_le_bytes().as_ref()],
+        bump = vault_auth.vault_auth_bump,
+    )]
+    pub vault_auth: Box<Account<'info, VaultAuth>>,
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = mpl_bubblegum::ID,
+    )]
+    /// CHECK: Bubblegum tree config of the merkle tree
+    pub tree_config: UncheckedAccount<'info>,
+    #[account(mut, owner = SPL_ACCOUNT_COMPRESSION_ID)]
+    /// CHECK: Account compression program checks the tree
+    pub merkle_tree: UncheckedAccount<'info>,
+    #[account(address = SPL_NOOP_ID)]
+    /// CHECK: This is the SPL noop program
+    pub log_wrapper: UncheckedAccount<'info>,
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    /// CHECK: This is the SPL account compression program
+    pub compression_program: UncheckedAccount<'info>,
+    #[account(address = mpl_bubblegum::ID)]
+    /// CHECK: This is the Bubblegum program
+    pub bubblegum_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreatorResolveInputCnft<'info> {
+    pub fn transfer_from_vault(
+        &self,
+        vault_seed: u64,
+        leaf_info: &LeafInfo,
+        proof: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let vault_seed_bytes = vault_seed.to_le_bytes();
+        let seeds = &[
+            b"vaultAuth",
+            self.transmuter.to_account_info().key.as_ref(),
+            self.user.to_account_info().key.as_ref(),
+            vault_seed_bytes.as_ref(),
+            &[self.vault_auth.vault_auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let proof_accounts: Vec<(&AccountInfo<'info>, bool, bool)> =
+            proof.iter().map(|node| (node, false, false)).collect();
+
+        let creator = self.creator.to_account_info();
+        let vault_auth = self.vault_auth.to_account_info();
+        TransferCpiBuilder::new(&self.bubblegum_program)
+            .tree_config(&self.tree_config)
+            .leaf_owner(&vault_auth, true)
+            .leaf_delegate(&vault_auth, false)
+            .new_leaf_owner(&creator)
+            .merkle_tree(&self.merkle_tree)
+            .log_wrapper(&self.log_wrapper)
+            .compression_program(&self.compression_program)
+            .system_program(&self.system_program)
+            .root(leaf_info.root)
+            .data_hash(leaf_info.data_hash)
+            .creator_hash(leaf_info.creator_hash)
+            .nonce(leaf_info.nonce)
+            .index(leaf_info.index)
+            .add_remaining_accounts(&proof_accounts)
+            .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+
+    pub fn burn_from_vault(
+        &self,
+        vault_seed: u64,
+        leaf_info: &LeafInfo,
+        proof: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let vault_seed_bytes = vault_seed.to_le_bytes();
+        let seeds = &[
+            b"vaultAuth",
+            self.transmuter.to_account_info().key.as_ref(),
+            self.user.to_account_info().key.as_ref(),
+            vault_seed_bytes.as_ref(),
+            &[self.vault_auth.vault_auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let proof_accounts: Vec<(&AccountInfo<'info>, bool, bool)> =
+            proof.iter().map(|node| (node, false, false)).collect();
+
+        let vault_auth = self.vault_auth.to_account_info();
+        BurnCpiBuilder::new(&self.bubblegum_program)
+            .tree_config(&self.tree_config)
+            .leaf_owner(&vault_auth, true)
+            .leaf_delegate(&vault_auth, false)
+            .merkle_tree(&self.merkle_tree)
+            .log_wrapper(&self.log_wrapper)
+            .compression_program(&self.compression_program)
+            .system_program(&self.system_program)
+            .root(leaf_info.root)
+            .data_hash(leaf_info.data_hash)
+            .creator_hash(leaf_info.creator_hash)
+            .nonce(leaf_info.nonce)
+            .index(leaf_info.index)
+            .add_remaining_accounts(&proof_accounts)
+            .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+
+    pub fn tip_cranker(&self) -> Result<()> {
+        pay_resolve_tip(
+            &self.vault_auth.to_account_info(),
+            &self.cranker.to_account_info(),
+        )
+    }
+}
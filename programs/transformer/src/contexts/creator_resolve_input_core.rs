@@ -0,0 +1,98 @@
+use crate::structs::Transmuter;
+use crate::{core_burn_ix, core_transfer_ix, pay_resolve_tip, TransmuterError, VaultAuth};
+use crate::{MPL_CORE_ID, ROLE_RESOLVE};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+
+#[derive(Accounts)]
+#[instruction(seed: u64, vault_seed: u64)]
+pub struct CreatorResolveInputCore<'info> {
+    #[account(
+        mut,
+        constraint = !transmuter.creator_resolve_only || transmuter.has_role(cranker.key, ROLE_RESOLVE) @ TransmuterError::CreatorResolveOnly,
+    )]
+    pub cranker: Signer<'info>,
+    #[account(mut, constraint = *creator.to_account_info().key == transmuter.authority)]
+    pub creator: SystemAccount<'info>,
+    #[account(mut, constraint = *user.to_account_info().key == vault_auth.user)]
+    pub user: SystemAccount<'info>,
+    #[account(mut, owner = MPL_CORE_ID)]
+    /// CHECK: Checked against the vault auth handled inputs
+    pub asset: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Core program checks the asset collection
+    pub collection: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        mut,
+        seeds = [b"vaultAuth", transmuter.key().as_ref(), user.key.as_ref(), vault_seed.to_le_bytes().as_ref()],
+        bump = vault_auth.vault_auth_bump,
+    )]
+    pub vault_auth: Box<Account<'info, VaultAuth>>,
+    #[account(address = MPL_CORE_ID)]
+    /// CHECK: This is the Metaplex Core program
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreatorResolveInputCore<'info> {
+    fn invoke_as_vault_auth(&self, ix: &Instruction, vault_seed: u64) -> Result<()> {
+        let vault_seed_bytes = vault_seed.to_le_bytes();
+        let seeds = &[
+            b"vaultAuth",
+            self.transmuter.to_account_info().key.as_ref(),
+            self.user.to_account_info().key.as_ref(),
+            vault_seed_bytes.as_ref(),
+            &[self.vault_auth.vault_auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let mut account_infos = vec![
+            self.asset.to_account_info(),
+            self.cranker.to_account_info(),
+            self.creator.to_account_info(),
+            self.vault_auth.to_account_info(),
+            self.core_program.to_account_info(),
+        ];
+        if let Some(collection) = &self.collection {
+            account_infos.push(collection.to_account_info());
+        }
+
+        invoke_signed(ix, &account_infos, signer_seeds)?;
+        Ok(())
+    }
+
+    pub fn transfer_from_vault(&self, vault_seed: u64) -> Result<()> {
+        let ix = core_transfer_ix(
+            self.asset.key(),
+            self.collection.as_ref().map(|collection| collection.key()),
+            self.cranker.key(),
+            self.vault_auth.key(),
+            self.creator.key(),
+        );
+        self.invoke_as_vault_auth(&ix, vault_seed)
+    }
+
+    pub fn burn_from_vault(&self, vault_seed: u64) -> Result<()> {
+        let ix = core_burn_ix(
+            self.asset.key(),
+            self.collection.as_ref().map(|collection| collection.key()),
+            self.cranker.key(),
+            self.vault_auth.key(),
+        );
+        self.invoke_as_vault_auth(&ix, vault_seed)
+    }
+
+    pub fn tip_cranker(&self) -> Result<()> {
+        pay_resolve_tip(
+            &self.vault_auth.to_account_info(),
+            &self.cranker.to_account_info(),
+        )
+    }
+}
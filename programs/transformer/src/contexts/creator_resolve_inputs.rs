@@ -0,0 +1,93 @@
+use crate::structs::Transmuter;
+use crate::{TransmuterError, VaultAuth, RESOLVE_TIP_LAMPORTS, ROLE_RESOLVE};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, close_account, transfer, Burn, CloseAccount, Token, Transfer};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct CreatorResolveInputs<'info> {
+    #[account(
+        mut,
+        constraint = !transmuter.creator_resolve_only || transmuter.has_role(cranker.key, ROLE_RESOLVE) @ TransmuterError::CreatorResolveOnly,
+    )]
+    pub cranker: Signer<'info>,
+    #[account(constraint = *creator.to_account_info().key == transmuter.authority)]
+    pub creator: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    pub token_program: Program<'info, Token>,
+}
+
+//Input accounts are read from remaining accounts as (vault_auth, user, mint, vault, creator_ata)
+impl<'info> CreatorResolveInputs<'info> {
+    pub fn resolve_input(
+        &self,
+        vault_auth: &Account<'info, VaultAuth>,
+        user: &AccountInfo<'info>,
+        mint: &AccountInfo<'info>,
+        vault: &AccountInfo<'info>,
+        creator_ata: &AccountInfo<'info>,
+        method: &str,
+    ) -> Result<()> {
+        let transmuter_key = self.transmuter.key();
+        let vault_seed_bytes = vault_auth.seed.to_le_bytes();
+        let seeds = &[
+            b"vaultAuth",
+            transmuter_key.as_ref(),
+            user.key.as_ref(),
+            vault_seed_bytes.as_ref(),
+            &[vault_auth.vault_auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_program = self.token_program.to_account_info();
+        match method {
+            "burn" => {
+                let cpi_accounts = Burn {
+                    mint: mint.clone(),
+                    from: vault.clone(),
+                    authority: vault_auth.to_account_info(),
+                };
+                burn(
+                    CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds),
+                    1,
+                )?;
+            }
+            "transfer" => {
+                let cpi_accounts = Transfer {
+                    from: vault.clone(),
+                    to: creator_ata.clone(),
+                    authority: vault_auth.to_account_info(),
+                };
+                transfer(
+                    CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds),
+                    1,
+                )?;
+            }
+            _ => return err!(TransmuterError::InvalidResolveMethod),
+        }
+
+        //Vault rent tips the cranker, the rest goes back to the user with the vault auth
+        let vault_rent = vault.lamports();
+        let cpi_accounts = CloseAccount {
+            account: vault.clone(),
+            destination: vault_auth.to_account_info(),
+            authority: vault_auth.to_account_info(),
+        };
+        close_account(CpiContext::new_with_signer(
+            cpi_program,
+            cpi_accounts,
+            signer_seeds,
+        ))?;
+
+        let tip = vault_rent.min(RESOLVE_TIP_LAMPORTS);
+        **vault_auth.to_account_info().try_borrow_mut_lamports()? -= tip;
+        **self.cranker.to_account_info().try_borrow_mut_lamports()? += tip;
+
+        Ok(())
+    }
+}
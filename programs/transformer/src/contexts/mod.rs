@@ -1,27 +1,79 @@
 pub mod creator_burn_input;
 pub mod creator_resolve_input;
+pub mod creator_resolve_input_cnft;
+pub mod creator_resolve_input_core;
+pub mod creator_resolve_inputs;
+pub mod transmuter_accept_authority;
+pub mod transmuter_add_pool;
+pub mod transmuter_cancel_sol;
 pub mod transmuter_cancel_spl;
 pub mod transmuter_close;
 pub mod transmuter_create;
 pub mod transmuter_create_holder;
+pub mod transmuter_revoke_spl_mint;
 pub mod transmuter_set;
+pub mod transmuter_set_pool;
+pub mod transmuter_set_print;
+pub mod transmuter_set_sol;
 pub mod transmuter_set_spl;
+pub mod transmuter_set_spl_mint;
+pub mod transmuter_withdraw_pool;
+pub mod user_abort_vault;
 pub mod user_cancel_input;
+pub mod user_cancel_input_cnft;
+pub mod user_cancel_input_core;
+pub mod user_claim_output_cnft;
+pub mod user_claim_output_core;
 pub mod user_claim_output_nft;
+pub mod user_claim_output_pool;
+pub mod user_claim_output_sol;
 pub mod user_claim_output_spl;
+pub mod user_claim_outputs;
 pub mod user_init_vault_auth;
 pub mod user_send_input;
+pub mod user_send_input_cnft;
+pub mod user_send_input_core;
+pub mod user_send_inputs;
+pub mod user_sync_vault;
+pub mod user_transmute;
+pub mod vault_refund;
 
 pub use creator_burn_input::*;
 pub use creator_resolve_input::*;
+pub use creator_resolve_input_cnft::*;
+pub use creator_resolve_input_core::*;
+pub use creator_resolve_inputs::*;
+pub use transmuter_accept_authority::*;
+pub use transmuter_add_pool::*;
+pub use transmuter_cancel_sol::*;
 pub use transmuter_cancel_spl::*;
 pub use transmuter_close::*;
 pub use transmuter_create::*;
 pub use transmuter_create_holder::*;
+pub use transmuter_revoke_spl_mint::*;
 pub use transmuter_set::*;
+pub use transmuter_set_pool::*;
+pub use transmuter_set_print::*;
+pub use transmuter_set_sol::*;
 pub use transmuter_set_spl::*;
+pub use transmuter_set_spl_mint::*;
+pub use transmuter_withdraw_pool::*;
+pub use user_abort_vault::*;
 pub use user_cancel_input::*;
+pub use user_cancel_input_cnft::*;
+pub use user_cancel_input_core::*;
+pub use user_claim_output_cnft::*;
+pub use user_claim_output_core::*;
 pub use user_claim_output_nft::*;
+pub use user_claim_output_pool::*;
+pub use user_claim_output_sol::*;
 pub use user_claim_output_spl::*;
+pub use user_claim_outputs::*;
 pub use user_init_vault_auth::*;
 pub use user_send_input::*;
+pub use user_send_input_cnft::*;
+pub use user_send_input_core::*;
+pub use user_send_inputs::*;
+pub use user_sync_vault::*;
+pub use user_transmute::*;
+pub use vault_refund::*;
@@ -0,0 +1,17 @@
+use crate::{Transmuter, TransmuterError};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct TransmuterAcceptAuthority<'info> {
+    #[account(
+        constraint = transmuter.pending_authority == Some(authority.key()) @ TransmuterError::InvalidPendingAuthority,
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+}
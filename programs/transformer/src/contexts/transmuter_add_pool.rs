@@ -0,0 +1,68 @@
+use crate::structs::{Pool, Transmuter};
+use crate::{TransmuterError, ROLE_EDIT};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer, Mint, Token, TokenAccount, Transfer},
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64, pool_seed: u64)]
+pub struct TransmuterAddPool<'info> {
+    #[account(
+        mut,
+        constraint = transmuter.has_role(creator.key, ROLE_EDIT) @ TransmuterError::MissingRole,
+    )]
+    pub creator: Signer<'info>,
+    #[account(
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        seeds = [b"auth", transmuter.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because this account doesn't exist
+    pub auth: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", transmuter.key().as_ref(), pool_seed.to_le_bytes().as_ref()],
+        bump = pool.pool_bump,
+        realloc = Pool::LEN + (pool.mints.len() + 1) * 32,
+        realloc::payer = creator,
+        realloc::zero = false,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+    #[account(constraint = mint.decimals == 0 && mint.supply == 1 @ TransmuterError::InvalidPool)]
+    pub mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = creator,
+    )]
+    pub creator_ata: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = auth,
+    )]
+    pub auth_ata: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TransmuterAddPool<'info> {
+    pub fn transfer_to_auth(&self) -> Result<()> {
+        let cpi_accounts = Transfer {
+            from: self.creator_ata.to_account_info(),
+            to: self.auth_ata.to_account_info(),
+            authority: self.creator.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        transfer(CpiContext::new(cpi_program, cpi_accounts), 1)
+    }
+}
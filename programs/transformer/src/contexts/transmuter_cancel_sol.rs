@@ -0,0 +1,28 @@
+use crate::structs::{SolEscrow, Transmuter};
+use crate::{TransmuterError, ROLE_WITHDRAW};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct TransmuterCancelSol<'info> {
+    #[account(
+        mut,
+        constraint = transmuter.has_role(creator.key, ROLE_WITHDRAW) @ TransmuterError::MissingRole,
+    )]
+    pub creator: Signer<'info>,
+    #[account(mut, address = transmuter.authority @ TransmuterError::NotAuthority)]
+    pub authority: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"solEscrow", transmuter.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.escrow_bump,
+    )]
+    pub escrow: Box<Account<'info, SolEscrow>>,
+}
@@ -1,15 +1,18 @@
-use crate::{structs::Transmuter, TransmuterError};
+use crate::{structs::Transmuter, TransmuterError, ROLE_WITHDRAW};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
 
 #[derive(Accounts)]
 #[instruction(seed: u64)]
 pub struct TransmuterCancelSpl<'info> {
-    #[account(mut, constraint = *creator.to_account_info().key == transmuter.creator)]
+    #[account(
+        mut,
+        constraint = transmuter.has_role(creator.key, ROLE_WITHDRAW) @ TransmuterError::MissingRole,
+    )]
     pub creator: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"transmuter", creator.key.as_ref(), seed.to_le_bytes().as_ref()],
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
         bump = transmuter.transmuter_bump,
     )]
     pub transmuter: Box<Account<'info, Transmuter>>,
@@ -19,9 +22,18 @@ pub struct TransmuterCancelSpl<'info> {
     )]
     /// CHECK: This is not dangerous because this account doesn't exist
     pub auth: UncheckedAccount<'info>,
-    #[account(mut)]
+    pub mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = auth,
+    )]
     pub auth_ata: Account<'info, TokenAccount>,
-    #[account(mut)]
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = creator_ata.owner == transmuter.authority @ TransmuterError::InvalidCreatorAta,
+    )]
     pub creator_ata: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
 }
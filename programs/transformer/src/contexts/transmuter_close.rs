@@ -1,11 +1,151 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+use anchor_spl::token::{
+    close_account, set_authority, transfer, CloseAccount, SetAuthority, Token, TokenAccount,
+    Transfer,
+};
 
-use crate::Transmuter;
+use crate::{Pool, SolEscrow, Transmuter, TransmuterError};
 
 #[derive(Accounts)]
 pub struct TransmuterClose<'info> {
-    #[account(mut, constraint = *creator.to_account_info().key == transmuter.creator)]
+    #[account(mut, constraint = *creator.to_account_info().key == transmuter.authority)]
     pub creator: Signer<'info>,
     #[account(mut, close = creator)]
     pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        seeds = [b"auth", transmuter.key().as_ref()],
+        bump = transmuter.auth_bump,
+    )]
+    /// CHECK: This is not dangerous because this account doesn't exist
+    pub auth: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+//Escrow accounts are read from remaining accounts, in output order
+impl<'info> TransmuterClose<'info> {
+    pub fn drain_auth_ata(
+        &self,
+        mint: &Pubkey,
+        auth_ata: &'info AccountInfo<'info>,
+        creator_ata: &'info AccountInfo<'info>,
+    ) -> Result<()> {
+        require!(
+            auth_ata.key() == get_associated_token_address(&self.auth.key(), mint),
+            TransmuterError::InvalidEscrow
+        );
+        let creator_token_account = Account::<TokenAccount>::try_from(creator_ata)?;
+        require!(
+            creator_token_account.owner == self.creator.key()
+                && creator_token_account.mint == *mint,
+            TransmuterError::InvalidCreatorAta
+        );
+        let amount = Account::<TokenAccount>::try_from(auth_ata)?.amount;
+
+        let seeds = &[
+            b"auth",
+            self.transmuter.to_account_info().key.as_ref(),
+            &[self.transmuter.auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: auth_ata.clone(),
+            to: creator_ata.clone(),
+            authority: self.auth.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        transfer(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds),
+            amount,
+        )?;
+
+        let cpi_accounts = CloseAccount {
+            account: auth_ata.clone(),
+            destination: self.creator.to_account_info(),
+            authority: self.auth.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        close_account(CpiContext::new_with_signer(
+            cpi_program,
+            cpi_accounts,
+            signer_seeds,
+        ))
+    }
+
+    pub fn close_pool(
+        &self,
+        pool_account: &'info AccountInfo<'info>,
+        escrow_accounts: &mut std::slice::Iter<'info, AccountInfo<'info>>,
+    ) -> Result<()> {
+        let pool = Account::<Pool>::try_from(pool_account)?;
+        let pool_key = Pubkey::create_program_address(
+            &[
+                b"pool",
+                self.transmuter.key().as_ref(),
+                pool.seed.to_le_bytes().as_ref(),
+                &[pool.pool_bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| TransmuterError::InvalidPool)?;
+        require!(
+            pool_account.key() == pool_key && pool.transmuter == self.transmuter.key(),
+            TransmuterError::InvalidPool
+        );
+
+        for mint_key in pool.mints.iter() {
+            let (Some(auth_ata), Some(creator_ata)) =
+                (escrow_accounts.next(), escrow_accounts.next())
+            else {
+                return err!(TransmuterError::InvalidEscrow);
+            };
+            self.drain_auth_ata(mint_key, auth_ata, creator_ata)?;
+        }
+        pool.close(self.creator.to_account_info())
+    }
+
+    pub fn close_sol_escrow(&self, escrow_account: &'info AccountInfo<'info>) -> Result<()> {
+        let escrow = Account::<SolEscrow>::try_from(escrow_account)?;
+        let escrow_key = Pubkey::create_program_address(
+            &[
+                b"solEscrow",
+                self.transmuter.key().as_ref(),
+                escrow.seed.to_le_bytes().as_ref(),
+                &[escrow.escrow_bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| TransmuterError::InvalidEscrow)?;
+        require!(
+            escrow_account.key() == escrow_key && escrow.transmuter == self.transmuter.key(),
+            TransmuterError::InvalidEscrow
+        );
+
+        escrow.close(self.creator.to_account_info())
+    }
+
+    pub fn set_creator_as_authority(&self, mint: &AccountInfo<'info>) -> Result<()> {
+        let seeds = &[
+            b"auth",
+            self.transmuter.to_account_info().key.as_ref(),
+            &[self.transmuter.auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = SetAuthority {
+            current_authority: self.auth.to_account_info(),
+            account_or_mint: mint.clone(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        set_authority(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds),
+            AuthorityType::MintTokens,
+            Some(self.creator.key()),
+        )
+    }
 }
@@ -1,5 +1,5 @@
 use crate::errors::TransmuterError;
-use crate::structs::{Config, InputInfo, OutputInfo, Transmuter};
+use crate::structs::{Config, Transmuter};
 use crate::utils::parse_json;
 use anchor_lang::prelude::*;
 use std::str::FromStr;
@@ -14,7 +14,7 @@ pub struct TransmuterCreate<'info> {
         payer = creator,
         seeds = [b"transmuter", creator.key.as_ref(), seed.to_le_bytes().as_ref()],
         bump,
-        space =  Transmuter::LEN + parse_json::<Config>(&config_json).unwrap().input_length as usize * InputInfo::LEN + parse_json::<Config>(&config_json).unwrap().output_length as usize * OutputInfo::LEN,
+        space = parse_json::<Config>(&config_json).map_or(Transmuter::LEN, |config| Transmuter::space(&config)),
     )]
     pub transmuter: Box<Account<'info, Transmuter>>,
     #[account(
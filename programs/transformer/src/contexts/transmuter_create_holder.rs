@@ -1,7 +1,9 @@
-use crate::structs::{Config, InputInfo, OutputInfo, Transmuter};
+use crate::structs::{Config, Transmuter};
 use crate::utils::parse_json;
+use crate::TransmuterError;
 use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
+use mpl_token_metadata::accounts::Metadata;
 
 #[derive(Accounts)]
 #[instruction(seed: u64, config_json: String)]
@@ -13,7 +15,7 @@ pub struct TransmuterCreateHolder<'info> {
         payer = creator,
         seeds = [b"transmuter", creator.key.as_ref(), seed.to_le_bytes().as_ref()],
         bump,
-        space =  Transmuter::LEN + parse_json::<Config>(&config_json).unwrap().input_length as usize * InputInfo::LEN + parse_json::<Config>(&config_json).unwrap().output_length as usize * OutputInfo::LEN,
+        space = parse_json::<Config>(&config_json).map_or(Transmuter::LEN, |config| Transmuter::space(&config)),
     )]
     pub transmuter: Box<Account<'info, Transmuter>>,
     #[account(
@@ -24,8 +26,8 @@ pub struct TransmuterCreateHolder<'info> {
     pub auth: UncheckedAccount<'info>,
     #[account(mut)]
     pub holder_ata: Account<'info, TokenAccount>,
-    #[account(mut)]
-    /// CHECK: fix later
+    #[account(mut, address = Metadata::find_pda(&holder_ata.mint).0 @ TransmuterError::InvalidMetadata)]
+    /// CHECK: Address derived from the holder mint, Metaplex checks the content
     pub holder_metadata: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
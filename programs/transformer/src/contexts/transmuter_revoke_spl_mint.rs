@@ -0,0 +1,53 @@
+use crate::{structs::Transmuter, TransmuterError, ROLE_WITHDRAW};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{
+    set_authority, spl_token::instruction::AuthorityType, Mint, SetAuthority, Token,
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct TransmuterRevokeSplMint<'info> {
+    #[account(
+        mut,
+        constraint = transmuter.has_role(creator.key, ROLE_WITHDRAW) @ TransmuterError::MissingRole,
+    )]
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        seeds = [b"auth", transmuter.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because this account doesn't exist
+    pub auth: UncheckedAccount<'info>,
+    #[account(mut, mint::authority = auth)]
+    pub mint: Box<Account<'info, Mint>>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> TransmuterRevokeSplMint<'info> {
+    pub fn set_creator_as_authority(&self) -> Result<()> {
+        let seeds = &[
+            b"auth",
+            self.transmuter.to_account_info().key.as_ref(),
+            &[self.transmuter.auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = SetAuthority {
+            current_authority: self.auth.to_account_info(),
+            account_or_mint: self.mint.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        set_authority(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds),
+            AuthorityType::MintTokens,
+            Some(self.transmuter.authority),
+        )
+    }
+}
@@ -1,14 +1,14 @@
-use crate::structs::Transmuter;
+use crate::{structs::Transmuter, TransmuterError};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 #[instruction(seed: u64)]
 pub struct TransmuterSet<'info> {
-    #[account(mut, constraint = *creator.to_account_info().key == transmuter.creator)]
+    #[account(mut, constraint = transmuter.is_operator(creator.key) @ TransmuterError::MissingRole)]
     pub creator: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"transmuter", creator.key.as_ref(), seed.to_le_bytes().as_ref()],
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
         bump = transmuter.transmuter_bump,
     )]
     pub transmuter: Box<Account<'info, Transmuter>>,
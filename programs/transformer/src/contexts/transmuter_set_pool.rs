@@ -0,0 +1,28 @@
+use crate::structs::{Pool, Transmuter};
+use crate::{TransmuterError, ROLE_EDIT};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(seed: u64, pool_seed: u64)]
+pub struct TransmuterSetPool<'info> {
+    #[account(
+        mut,
+        constraint = transmuter.has_role(creator.key, ROLE_EDIT) @ TransmuterError::MissingRole,
+    )]
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"pool", transmuter.key().as_ref(), pool_seed.to_le_bytes().as_ref()],
+        bump,
+        space = Pool::LEN,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,65 @@
+use crate::{structs::Transmuter, TransmuterError, ROLE_EDIT};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer, Mint, Token, TokenAccount, Transfer},
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct TransmuterSetPrint<'info> {
+    #[account(
+        mut,
+        constraint = transmuter.has_role(creator.key, ROLE_EDIT) @ TransmuterError::MissingRole,
+    )]
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        seeds = [b"auth", transmuter.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because this account doesn't exist
+    pub auth: UncheckedAccount<'info>,
+    pub master_mint: Box<Account<'info, Mint>>,
+    #[account(
+        seeds = [b"metadata", mpl_token_metadata::ID.as_ref(), master_mint.key().as_ref(), b"edition"],
+        bump,
+        seeds::program = mpl_token_metadata::ID,
+    )]
+    /// CHECK: Deserialized as a master edition
+    pub master_edition: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        token::mint = master_mint,
+        token::authority = creator,
+    )]
+    pub creator_ata: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = master_mint,
+        associated_token::authority = auth,
+    )]
+    pub auth_ata: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TransmuterSetPrint<'info> {
+    pub fn transfer_to_auth(&self) -> Result<()> {
+        let cpi_accounts = Transfer {
+            from: self.creator_ata.to_account_info(),
+            to: self.auth_ata.to_account_info(),
+            authority: self.creator.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        transfer(CpiContext::new(cpi_program, cpi_accounts), 1)
+    }
+}
@@ -0,0 +1,41 @@
+use crate::structs::{SolEscrow, Transmuter};
+use crate::{TransmuterError, ROLE_EDIT};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+#[derive(Accounts)]
+#[instruction(seed: u64, escrow_seed: u64)]
+pub struct TransmuterSetSol<'info> {
+    #[account(
+        mut,
+        constraint = transmuter.has_role(creator.key, ROLE_EDIT) @ TransmuterError::MissingRole,
+    )]
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        init_if_needed,
+        payer = creator,
+        seeds = [b"solEscrow", transmuter.key().as_ref(), escrow_seed.to_le_bytes().as_ref()],
+        bump,
+        space = SolEscrow::LEN,
+    )]
+    pub escrow: Box<Account<'info, SolEscrow>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TransmuterSetSol<'info> {
+    pub fn transfer_to_escrow(&self, amount: u64) -> Result<()> {
+        let cpi_accounts = Transfer {
+            from: self.creator.to_account_info(),
+            to: self.escrow.to_account_info(),
+        };
+
+        let cpi_program = self.system_program.to_account_info();
+        transfer(CpiContext::new(cpi_program, cpi_accounts), amount)
+    }
+}
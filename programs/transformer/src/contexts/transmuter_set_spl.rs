@@ -1,23 +1,43 @@
-use crate::{structs::Transmuter, TransmuterError};
+use crate::{structs::Transmuter, TransmuterError, ROLE_EDIT};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer, Mint, Token, TokenAccount, Transfer},
+};
 
 #[derive(Accounts)]
 #[instruction(seed: u64)]
 pub struct TransmuterSetSpl<'info> {
-    #[account(mut, constraint = *creator.to_account_info().key == transmuter.creator)]
+    #[account(
+        mut,
+        constraint = transmuter.has_role(creator.key, ROLE_EDIT) @ TransmuterError::MissingRole,
+    )]
     pub creator: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"transmuter", creator.key.as_ref(), seed.to_le_bytes().as_ref()],
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
         bump = transmuter.transmuter_bump,
     )]
     pub transmuter: Box<Account<'info, Transmuter>>,
-    #[account(mut)]
+    #[account(
+        seeds = [b"auth", transmuter.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because this account doesn't exist
+    pub auth: UncheckedAccount<'info>,
+    pub mint: Box<Account<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = auth,
+    )]
     pub auth_ata: Account<'info, TokenAccount>,
-    #[account(mut)]
+    #[account(mut, token::mint = mint)]
     pub creator_ata: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
 impl<'info> TransmuterSetSpl<'info> {
@@ -0,0 +1,46 @@
+use crate::{structs::Transmuter, TransmuterError, ROLE_EDIT};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{
+    set_authority, spl_token::instruction::AuthorityType, Mint, SetAuthority, Token,
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct TransmuterSetSplMint<'info> {
+    #[account(
+        mut,
+        constraint = transmuter.has_role(creator.key, ROLE_EDIT) @ TransmuterError::MissingRole,
+    )]
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        seeds = [b"auth", transmuter.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because this account doesn't exist
+    pub auth: UncheckedAccount<'info>,
+    #[account(mut, mint::authority = creator)]
+    pub mint: Box<Account<'info, Mint>>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> TransmuterSetSplMint<'info> {
+    pub fn set_auth_as_authority(&self) -> Result<()> {
+        let cpi_accounts = SetAuthority {
+            current_authority: self.creator.to_account_info(),
+            account_or_mint: self.mint.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        set_authority(
+            CpiContext::new(cpi_program, cpi_accounts),
+            AuthorityType::MintTokens,
+            Some(self.auth.key()),
+        )
+    }
+}
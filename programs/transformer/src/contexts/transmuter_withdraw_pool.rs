@@ -0,0 +1,89 @@
+use crate::structs::{Pool, Transmuter};
+use crate::{TransmuterError, ROLE_WITHDRAW};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{close_account, transfer, CloseAccount, Mint, Token, TokenAccount, Transfer},
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64, pool_seed: u64)]
+pub struct TransmuterWithdrawPool<'info> {
+    #[account(
+        mut,
+        constraint = transmuter.has_role(creator.key, ROLE_WITHDRAW) @ TransmuterError::MissingRole,
+    )]
+    pub creator: Signer<'info>,
+    #[account(mut, address = transmuter.authority @ TransmuterError::NotAuthority)]
+    pub authority: SystemAccount<'info>,
+    #[account(
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        seeds = [b"auth", transmuter.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because this account doesn't exist
+    pub auth: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"pool", transmuter.key().as_ref(), pool_seed.to_le_bytes().as_ref()],
+        bump = pool.pool_bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+    pub mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = auth,
+    )]
+    pub auth_ata: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = authority,
+    )]
+    pub creator_ata: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TransmuterWithdrawPool<'info> {
+    pub fn transfer_from_auth(&self) -> Result<()> {
+        let seeds = &[
+            b"auth",
+            self.transmuter.to_account_info().key.as_ref(),
+            &[self.transmuter.auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: self.auth_ata.to_account_info(),
+            to: self.creator_ata.to_account_info(),
+            authority: self.auth.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        transfer(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds),
+            1,
+        )?;
+
+        let cpi_accounts = CloseAccount {
+            account: self.auth_ata.to_account_info(),
+            destination: self.authority.to_account_info(),
+            authority: self.auth.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        close_account(CpiContext::new_with_signer(
+            cpi_program,
+            cpi_accounts,
+            signer_seeds,
+        ))
+    }
+}
@@ -0,0 +1,114 @@
+use crate::structs::Transmuter;
+use crate::TransmuterError;
+use crate::VaultAuth;
+use anchor_lang::prelude::*;
+use mpl_bubblegum::instructions::MintToCollectionV1CpiBuilder;
+use mpl_bubblegum::programs::{SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID};
+use mpl_bubblegum::types::MetadataArgs;
+
+#[derive(Accounts)]
+#[instruction(seed: u64, vault_seed: u64)]
+pub struct UserClaimOutputCnft<'info> {
+    #[account(mut, constraint = *creator.to_account_info().key == transmuter.authority)]
+    pub creator: SystemAccount<'info>,
+    #[account(mut, constraint = *user.to_account_info().key == vault_auth.user)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        seeds = [b"auth", transmuter.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because this account doesn't exist
+    pub auth: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vaultAuth", transmuter.key().as_ref(), user.key.as_ref(), vault_seed.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault_auth: Box<Account<'info, VaultAuth>>,
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = mpl_bubblegum::ID,
+    )]
+    /// CHECK: Bubblegum tree config of the merkle tree
+    pub tree_config: UncheckedAccount<'info>,
+    #[account(mut, owner = SPL_ACCOUNT_COMPRESSION_ID)]
+    /// CHECK: Checked against the output merkle tree
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: Checked against the output collection
+    pub collection_mint: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Metaplex will check this
+    pub collection_metadata: UncheckedAccount<'info>,
+    /// CHECK: Metaplex will check this
+    pub collection_edition: UncheckedAccount<'info>,
+    /// CHECK: Metaplex will check this
+    pub collection_authority_record_pda: Option<UncheckedAccount<'info>>,
+    #[account(
+        seeds = [b"collection_cpi"],
+        bump,
+        seeds::program = mpl_bubblegum::ID,
+    )]
+    /// CHECK: Bubblegum signer for collection CPIs
+    pub bubblegum_signer: UncheckedAccount<'info>,
+    #[account(address = SPL_NOOP_ID)]
+    /// CHECK: This is the SPL noop program
+    pub log_wrapper: UncheckedAccount<'info>,
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    /// CHECK: This is the SPL account compression program
+    pub compression_program: UncheckedAccount<'info>,
+    #[account(address = mpl_token_metadata::ID @ TransmuterError::InvalidTokenMetadataProgram)]
+    /// CHECK: This is the token metadata program
+    pub token_metadata_program: UncheckedAccount<'info>,
+    #[account(address = mpl_bubblegum::ID)]
+    /// CHECK: This is the Bubblegum program
+    pub bubblegum_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UserClaimOutputCnft<'info> {
+    pub fn mint_to_collection(&self, metadata: MetadataArgs) -> Result<()> {
+        let seeds = &[
+            &b"auth"[..],
+            &self.transmuter.key().to_bytes()[..],
+            &[self.transmuter.auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let user = self.user.to_account_info();
+        let auth = self.auth.to_account_info();
+        let collection_authority_record_pda = self
+            .collection_authority_record_pda
+            .as_ref()
+            .map(|record| record.to_account_info());
+
+        MintToCollectionV1CpiBuilder::new(&self.bubblegum_program)
+            .tree_config(&self.tree_config)
+            .leaf_owner(&user)
+            .leaf_delegate(&user)
+            .merkle_tree(&self.merkle_tree)
+            .payer(&user)
+            .tree_creator_or_delegate(&auth)
+            .collection_authority(&auth)
+            .collection_authority_record_pda(collection_authority_record_pda.as_ref())
+            .collection_mint(&self.collection_mint)
+            .collection_metadata(&self.collection_metadata)
+            .collection_edition(&self.collection_edition)
+            .bubblegum_signer(&self.bubblegum_signer)
+            .log_wrapper(&self.log_wrapper)
+            .compression_program(&self.compression_program)
+            .token_metadata_program(&self.token_metadata_program)
+            .system_program(&self.system_program)
+            .metadata(metadata)
+            .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+}
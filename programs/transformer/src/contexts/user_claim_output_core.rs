@@ -0,0 +1,85 @@
+use crate::structs::Transmuter;
+use crate::{core_create_ix, VaultAuth, MPL_CORE_ID};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+
+#[derive(Accounts)]
+#[instruction(seed: u64, vault_seed: u64)]
+pub struct UserClaimOutputCore<'info> {
+    #[account(mut, constraint = *creator.to_account_info().key == transmuter.authority)]
+    pub creator: SystemAccount<'info>,
+    #[account(mut, constraint = *user.to_account_info().key == vault_auth.user)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        seeds = [b"auth", transmuter.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because this account doesn't exist
+    pub auth: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vaultAuth", transmuter.key().as_ref(), user.key.as_ref(), vault_seed.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault_auth: Box<Account<'info, VaultAuth>>,
+    #[account(mut)]
+    pub asset: Signer<'info>,
+    #[account(mut, owner = MPL_CORE_ID)]
+    /// CHECK: Checked against the output collection
+    pub collection: Option<UncheckedAccount<'info>>,
+    #[account(address = MPL_CORE_ID)]
+    /// CHECK: This is the Metaplex Core program
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UserClaimOutputCore<'info> {
+    pub fn create_asset(&self, name: &str, uri: &str, traits: &[(String, String)]) -> Result<()> {
+        let seeds = &[
+            &b"auth"[..],
+            &self.transmuter.key().to_bytes()[..],
+            &[self.transmuter.auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        //Assets without a collection are handed to the creator
+        let collection = self.collection.as_ref().map(|collection| collection.key());
+        let update_authority = match collection {
+            Some(_) => None,
+            None => Some(self.creator.key()),
+        };
+
+        let ix = core_create_ix(
+            self.asset.key(),
+            collection,
+            self.auth.key(),
+            self.user.key(),
+            self.user.key(),
+            update_authority,
+            name,
+            uri,
+            traits,
+        )?;
+
+        let mut account_infos = vec![
+            self.asset.to_account_info(),
+            self.auth.to_account_info(),
+            self.user.to_account_info(),
+            self.creator.to_account_info(),
+            self.system_program.to_account_info(),
+            self.core_program.to_account_info(),
+        ];
+        if let Some(collection) = &self.collection {
+            account_infos.push(collection.to_account_info());
+        }
+
+        invoke_signed(&ix, &account_infos, signer_seeds)?;
+        Ok(())
+    }
+}
@@ -1,29 +1,33 @@
 use std::str::FromStr;
 
 use crate::structs::Transmuter;
-use crate::VaultAuth;
+use crate::{TransmuterError, VaultAuth};
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{mint_to, Mint, MintTo, Token, TokenAccount},
 };
 
+use mpl_token_metadata::accounts::{MasterEdition, Metadata};
 use mpl_token_metadata::instructions::{
     CreateMasterEditionV3CpiBuilder, CreateMetadataAccountV3CpiBuilder,
-    SetAndVerifyCollectionCpiBuilder, UpdateV1CpiBuilder, VerifyCreatorV1CpiBuilder,
+    MintNewEditionFromMasterEditionViaTokenCpiBuilder, SetAndVerifyCollectionCpiBuilder,
+    UpdateV1CpiBuilder, VerifyCreatorV1CpiBuilder,
+};
+use mpl_token_metadata::types::{
+    Collection, Creator, DataV2, MintNewEditionFromMasterEditionViaTokenArgs,
 };
-use mpl_token_metadata::types::{Collection, Creator, DataV2};
 
 #[derive(Accounts)]
 #[instruction(seed: u64, vault_seed: u64)]
 pub struct UserClaimOutputNft<'info> {
-    #[account(mut, constraint = *creator.to_account_info().key == transmuter.creator)]
+    #[account(mut, constraint = *creator.to_account_info().key == transmuter.authority)]
     pub creator: SystemAccount<'info>,
     #[account(mut, constraint = *user.to_account_info().key == vault_auth.user)]
     pub user: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"transmuter", creator.key.as_ref(), seed.to_le_bytes().as_ref()],
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
         bump = transmuter.transmuter_bump,
     )]
     pub transmuter: Box<Account<'info, Transmuter>>,
@@ -41,16 +45,20 @@ pub struct UserClaimOutputNft<'info> {
     pub vault_auth: Box<Account<'info, VaultAuth>>,
     #[account(mut)]
     pub mint: Box<Account<'info, Mint>>,
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = ata.owner == user.key() && ata.mint == mint.key() @ TransmuterError::InvalidUserAta,
+    )]
     pub ata: Account<'info, TokenAccount>,
-    #[account(mut)]
-    /// CHECK: fix later
+    #[account(mut, address = Metadata::find_pda(&mint.key()).0 @ TransmuterError::InvalidMetadata)]
+    /// CHECK: Address derived from the mint, Metaplex checks the content
     pub metadata: UncheckedAccount<'info>,
-    #[account(mut)]
-    /// CHECK: fix later
+    #[account(mut, address = MasterEdition::find_pda(&mint.key()).0 @ TransmuterError::InvalidEdition)]
+    /// CHECK: Address derived from the mint, Metaplex checks the content
     pub master_edition: UncheckedAccount<'info>,
     pub token_program: Program<'info, Token>,
-    /// CHECK: Metaplex will check this
+    #[account(address = mpl_token_metadata::ID @ TransmuterError::InvalidTokenMetadataProgram)]
+    /// CHECK: This is the token metadata program
     pub token_metadata_program: UncheckedAccount<'info>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -58,6 +66,16 @@ pub struct UserClaimOutputNft<'info> {
     pub rent: AccountInfo<'info>,
     /// CHECK: This is not dangerous because we don't read or write from this account
     pub sysvar_instructions: AccountInfo<'info>,
+    /// CHECK: Metaplex will check this, only used for print outputs
+    pub source_metadata: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: Metaplex will check this, only used for print outputs
+    pub source_edition: Option<UncheckedAccount<'info>>,
+    #[account(token::authority = auth)]
+    pub source_ata: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(mut)]
+    /// CHECK: Metaplex will check this, only used for print outputs
+    pub edition_marker: Option<UncheckedAccount<'info>>,
 }
 
 impl<'info> UserClaimOutputNft<'info> {
@@ -198,6 +216,47 @@ impl<'info> UserClaimOutputNft<'info> {
         Ok(result?)
     }
 
+    pub fn create_print(&self, edition: u64) -> Result<()> {
+        let seeds = &[
+            &b"auth"[..],
+            &self.transmuter.key().to_bytes()[..],
+            &[self.transmuter.auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let (Some(source_metadata), Some(source_edition), Some(source_ata), Some(edition_marker)) = (
+            &self.source_metadata,
+            &self.source_edition,
+            &self.source_ata,
+            &self.edition_marker,
+        ) else {
+            return err!(TransmuterError::InvalidMasterEdition);
+        };
+
+        let result =
+            MintNewEditionFromMasterEditionViaTokenCpiBuilder::new(&self.token_metadata_program)
+                .new_metadata(&self.metadata)
+                .new_edition(&self.master_edition)
+                .master_edition(source_edition)
+                .new_mint(&self.mint.to_account_info())
+                .edition_mark_pda(edition_marker)
+                .new_mint_authority(&self.auth)
+                .payer(&self.user)
+                .token_account_owner(&self.auth)
+                .token_account(&source_ata.to_account_info())
+                .new_metadata_update_authority(&self.creator)
+                .metadata(source_metadata)
+                .token_program(&self.token_program)
+                .system_program(&self.system_program)
+                .rent(Some(&self.rent))
+                .mint_new_edition_from_master_edition_via_token_args(
+                    MintNewEditionFromMasterEditionViaTokenArgs { edition },
+                )
+                .invoke_signed(signer_seeds);
+
+        Ok(result?)
+    }
+
     // pub fn set_collection(&self, collection_string: &Option<String>) -> Result<()> {
     //     let collection_mint = Pubkey::from_str(collection_string).unwrap();
 
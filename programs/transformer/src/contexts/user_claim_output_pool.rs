@@ -0,0 +1,105 @@
+use crate::structs::{Pool, Transmuter};
+use crate::{TransmuterError, VaultAuth};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{close_account, transfer, CloseAccount, Mint, Token, TokenAccount, Transfer},
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64, vault_seed: u64)]
+pub struct UserClaimOutputPool<'info> {
+    #[account(mut, constraint = *creator.to_account_info().key == transmuter.authority)]
+    pub creator: SystemAccount<'info>,
+    #[account(mut, constraint = *user.to_account_info().key == vault_auth.user)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        seeds = [b"auth", transmuter.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because this account doesn't exist
+    pub auth: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vaultAuth", transmuter.key().as_ref(), user.key.as_ref(), vault_seed.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault_auth: Box<Account<'info, VaultAuth>>,
+    #[account(
+        mut,
+        seeds = [b"pool", transmuter.key().as_ref(), pool.seed.to_le_bytes().as_ref()],
+        bump = pool.pool_bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+    pub mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = auth,
+    )]
+    pub auth_ata: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+    )]
+    pub user_ata: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UserClaimOutputPool<'info> {
+    //Shuffled pools pick from the seed the vault committed when it opened
+    pub fn pick_index(&self) -> Result<usize> {
+        let pool_length = self.pool.mints.len();
+        require!(pool_length > 0, TransmuterError::PoolDepleted);
+        if !self.pool.shuffle {
+            return Ok(0);
+        }
+
+        Ok((self.vault_auth.pick_seed % pool_length as u64) as usize)
+    }
+
+    pub fn transfer_from_auth(&self) -> Result<()> {
+        let seeds = &[
+            b"auth",
+            self.transmuter.to_account_info().key.as_ref(),
+            &[self.transmuter.auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: self.auth_ata.to_account_info(),
+            to: self.user_ata.to_account_info(),
+            authority: self.auth.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        transfer(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds),
+            1,
+        )?;
+
+        //Creator paid for the pool token account
+        let cpi_accounts = CloseAccount {
+            account: self.auth_ata.to_account_info(),
+            destination: self.creator.to_account_info(),
+            authority: self.auth.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        close_account(CpiContext::new_with_signer(
+            cpi_program,
+            cpi_accounts,
+            signer_seeds,
+        ))
+    }
+}
@@ -0,0 +1,44 @@
+use crate::structs::{SolEscrow, Transmuter};
+use crate::{TransmuterError, VaultAuth};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(seed: u64, vault_seed: u64)]
+pub struct UserClaimOutputSol<'info> {
+    #[account(mut, constraint = *creator.to_account_info().key == transmuter.authority)]
+    pub creator: SystemAccount<'info>,
+    #[account(mut, constraint = *user.to_account_info().key == vault_auth.user)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        mut,
+        seeds = [b"vaultAuth", transmuter.key().as_ref(), user.key.as_ref(), vault_seed.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault_auth: Box<Account<'info, VaultAuth>>,
+    #[account(
+        mut,
+        seeds = [b"solEscrow", transmuter.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.escrow_bump,
+    )]
+    pub escrow: Box<Account<'info, SolEscrow>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UserClaimOutputSol<'info> {
+    pub fn transfer_from_escrow(&self, amount: u64) -> Result<()> {
+        let escrow = self.escrow.to_account_info();
+        let balance = SolEscrow::balance(&escrow)?;
+        require!(balance >= amount, TransmuterError::InsufficientEscrow);
+
+        //Escrow is owned by the program, lamports move directly
+        **escrow.try_borrow_mut_lamports()? -= amount;
+        **self.user.to_account_info().try_borrow_mut_lamports()? += amount;
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@ use std::str::FromStr;
 use crate::structs::Transmuter;
 use crate::{TransmuterError, VaultAuth};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+use anchor_spl::token::{mint_to, transfer, Mint, MintTo, Token, TokenAccount, Transfer};
 
 use mpl_token_metadata::instructions::{
     CreateMasterEditionV3CpiBuilder, CreateMetadataAccountV3CpiBuilder, UpdateV1CpiBuilder,
@@ -14,13 +14,13 @@ use mpl_token_metadata::types::{Collection, Creator, DataV2};
 #[derive(Accounts)]
 #[instruction(seed: u64, vault_seed: u64)]
 pub struct UserClaimOutputSpl<'info> {
-    #[account(mut, constraint = *creator.to_account_info().key == transmuter.creator)]
+    #[account(mut, constraint = *creator.to_account_info().key == transmuter.authority)]
     pub creator: SystemAccount<'info>,
     #[account(mut, constraint = *user.to_account_info().key == vault_auth.user)]
     pub user: Signer<'info>,
     #[account(
         mut,
-        seeds = [b"transmuter", creator.key.as_ref(), seed.to_le_bytes().as_ref()],
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
         bump = transmuter.transmuter_bump,
     )]
     pub transmuter: Box<Account<'info, Transmuter>>,
@@ -37,8 +37,14 @@ pub struct UserClaimOutputSpl<'info> {
     )]
     pub vault_auth: Box<Account<'info, VaultAuth>>,
     #[account(mut)]
-    pub auth_ata: Account<'info, TokenAccount>,
-    #[account(mut)]
+    pub mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = auth,
+    )]
+    pub auth_ata: Option<Account<'info, TokenAccount>>,
+    #[account(mut, token::mint = mint)]
     pub user_ata: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
 }
@@ -52,8 +58,12 @@ impl<'info> UserClaimOutputSpl<'info> {
         ];
         let signer_seeds = &[&seeds[..]];
 
+        let Some(auth_ata) = &self.auth_ata else {
+            return err!(TransmuterError::InvalidOutputMint);
+        };
+
         let cpi_accounts = Transfer {
-            from: self.auth_ata.to_account_info(),
+            from: auth_ata.to_account_info(),
             to: self.user_ata.to_account_info(),
             authority: self.auth.to_account_info(),
         };
@@ -65,4 +75,26 @@ impl<'info> UserClaimOutputSpl<'info> {
             amount,
         )
     }
+
+    pub fn mint_to_user(&self, &amount: &u64) -> Result<()> {
+        let seeds = &[
+            b"auth",
+            self.transmuter.to_account_info().key.as_ref(),
+            &[self.transmuter.auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = MintTo {
+            mint: self.mint.to_account_info(),
+            to: self.user_ata.to_account_info(),
+            authority: self.auth.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+
+        mint_to(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds),
+            amount,
+        )
+    }
 }
@@ -1,25 +1,30 @@
 use crate::VaultAuth;
 use crate::{structs::Transmuter, TransmuterError};
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+use mpl_token_metadata::accounts::Metadata;
 
 #[derive(Accounts)]
 #[instruction(seed: u64, vault_seed: u64)]
 pub struct UserSendInput<'info> {
-    #[account(mut, constraint = *creator.to_account_info().key == transmuter.creator)]
+    #[account(mut, constraint = *creator.to_account_info().key == transmuter.authority)]
     pub creator: SystemAccount<'info>,
     #[account(mut, constraint = *user.to_account_info().key == vault_auth.user)]
     pub user: Signer<'info>,
     #[account(mut)]
     pub mint: Box<Account<'info, Mint>>,
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = ata.owner == user.key() && ata.mint == mint.key() @ TransmuterError::InvalidUserAta,
+    )]
     pub ata: Account<'info, TokenAccount>,
-    #[account(mut)]
-    /// CHECK: fix later
+    #[account(mut, address = Metadata::find_pda(&mint.key()).0 @ TransmuterError::InvalidMetadata)]
+    /// CHECK: Address derived from the mint, Metaplex checks the content
     pub metadata: UncheckedAccount<'info>,
     #[account(
         mut,
-        seeds = [b"transmuter", creator.key.as_ref(), seed.to_le_bytes().as_ref()],
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
         bump = transmuter.transmuter_bump,
     )]
     pub transmuter: Box<Account<'info, Transmuter>>,
@@ -29,9 +34,16 @@ pub struct UserSendInput<'info> {
         bump = vault_auth.vault_auth_bump,
     )]
     pub vault_auth: Box<Account<'info, VaultAuth>>,
-    #[account(mut)]
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = mint,
+        associated_token::authority = vault_auth,
+    )]
     pub vault: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
 impl<'info> UserSendInput<'info> {
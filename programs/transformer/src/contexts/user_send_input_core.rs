@@ -0,0 +1,74 @@
+use crate::structs::Transmuter;
+use crate::{core_transfer_ix, VaultAuth, MPL_CORE_ID, RESOLVE_TIP_LAMPORTS};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::system_program::{transfer, Transfer};
+
+#[derive(Accounts)]
+#[instruction(seed: u64, vault_seed: u64)]
+pub struct UserSendInputCore<'info> {
+    #[account(mut, constraint = *creator.to_account_info().key == transmuter.authority)]
+    pub creator: SystemAccount<'info>,
+    #[account(mut, constraint = *user.to_account_info().key == vault_auth.user)]
+    pub user: Signer<'info>,
+    #[account(mut, owner = MPL_CORE_ID)]
+    /// CHECK: Deserialized as a Core asset by the matcher
+    pub asset: UncheckedAccount<'info>,
+    /// CHECK: Core program checks the asset collection
+    pub collection: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        mut,
+        seeds = [b"vaultAuth", transmuter.key().as_ref(), user.key.as_ref(), vault_seed.to_le_bytes().as_ref()],
+        bump = vault_auth.vault_auth_bump,
+    )]
+    pub vault_auth: Box<Account<'info, VaultAuth>>,
+    #[account(address = MPL_CORE_ID)]
+    /// CHECK: This is the Metaplex Core program
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UserSendInputCore<'info> {
+    pub fn transfer_to_vault(&self) -> Result<()> {
+        let ix = core_transfer_ix(
+            self.asset.key(),
+            self.collection.as_ref().map(|collection| collection.key()),
+            self.user.key(),
+            self.user.key(),
+            self.vault_auth.key(),
+        );
+
+        let mut account_infos = vec![
+            self.asset.to_account_info(),
+            self.user.to_account_info(),
+            self.vault_auth.to_account_info(),
+            self.core_program.to_account_info(),
+        ];
+        if let Some(collection) = &self.collection {
+            account_infos.push(collection.to_account_info());
+        }
+
+        invoke(&ix, &account_infos)?;
+        Ok(())
+    }
+
+    //No vault ata rent to tip the cranker from, so the user prepays the tip
+    pub fn fund_resolve_tip(&self) -> Result<()> {
+        let cpi_accounts = Transfer {
+            from: self.user.to_account_info(),
+            to: self.vault_auth.to_account_info(),
+        };
+        let cpi_program = self.system_program.to_account_info();
+
+        transfer(
+            CpiContext::new(cpi_program, cpi_accounts),
+            RESOLVE_TIP_LAMPORTS,
+        )
+    }
+}
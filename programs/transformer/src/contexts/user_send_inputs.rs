@@ -0,0 +1,44 @@
+use crate::structs::Transmuter;
+use crate::VaultAuth;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, Transfer};
+
+#[derive(Accounts)]
+#[instruction(seed: u64, vault_seed: u64)]
+pub struct UserSendInputs<'info> {
+    #[account(mut, constraint = *creator.to_account_info().key == transmuter.authority)]
+    pub creator: SystemAccount<'info>,
+    #[account(mut, constraint = *user.to_account_info().key == vault_auth.user)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        mut,
+        seeds = [b"vaultAuth", transmuter.key().as_ref(), user.key.as_ref(), vault_seed.to_le_bytes().as_ref()],
+        bump = vault_auth.vault_auth_bump,
+    )]
+    pub vault_auth: Box<Account<'info, VaultAuth>>,
+    pub token_program: Program<'info, Token>,
+}
+
+//Input accounts are read from remaining accounts as (mint, ata, metadata, vault)
+impl<'info> UserSendInputs<'info> {
+    pub fn transfer_to_vault(
+        &self,
+        ata: &AccountInfo<'info>,
+        vault: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let cpi_accounts = Transfer {
+            from: ata.clone(),
+            to: vault.clone(),
+            authority: self.user.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        transfer(CpiContext::new(cpi_program, cpi_accounts), 1)
+    }
+}
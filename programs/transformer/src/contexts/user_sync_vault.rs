@@ -0,0 +1,27 @@
+use crate::structs::Transmuter;
+use crate::VaultAuth;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(seed: u64, vault_seed: u64)]
+pub struct UserSyncVault<'info> {
+    #[account(constraint = *creator.to_account_info().key == transmuter.authority)]
+    pub creator: SystemAccount<'info>,
+    #[account(mut, constraint = *user.to_account_info().key == vault_auth.user)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        mut,
+        seeds = [b"vaultAuth", transmuter.key().as_ref(), user.key.as_ref(), vault_seed.to_le_bytes().as_ref()],
+        bump = vault_auth.vault_auth_bump,
+        realloc = VaultAuth::space(&transmuter.inputs, &transmuter.outputs),
+        realloc::payer = user,
+        realloc::zero = false,
+    )]
+    pub vault_auth: Box<Account<'info, VaultAuth>>,
+    pub system_program: Program<'info, System>,
+}
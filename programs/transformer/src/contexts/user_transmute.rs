@@ -0,0 +1,83 @@
+use crate::structs::Transmuter;
+use crate::OutputCpi;
+use crate::TransmuterError;
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{burn, transfer, Burn, Token, Transfer},
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct UserTransmute<'info> {
+    #[account(mut, constraint = *creator.to_account_info().key == transmuter.authority)]
+    pub creator: SystemAccount<'info>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        seeds = [b"auth", transmuter.key().as_ref()],
+        bump
+    )]
+    /// CHECK: This is not dangerous because this account doesn't exist
+    pub auth: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    #[account(address = mpl_token_metadata::ID @ TransmuterError::InvalidTokenMetadataProgram)]
+    /// CHECK: This is the Metaplex token metadata program
+    pub token_metadata_program: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub rent: AccountInfo<'info>,
+    /// CHECK: This is not dangerous because we don't read or write from this account
+    pub sysvar_instructions: AccountInfo<'info>,
+}
+
+//Inputs and outputs are read from remaining accounts, no vault is kept
+impl<'info> UserTransmute<'info> {
+    pub fn burn_input(&self, mint: &AccountInfo<'info>, ata: &AccountInfo<'info>) -> Result<()> {
+        let cpi_accounts = Burn {
+            mint: mint.clone(),
+            from: ata.clone(),
+            authority: self.user.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        burn(CpiContext::new(cpi_program, cpi_accounts), 1)
+    }
+
+    pub fn transfer_input(
+        &self,
+        ata: &AccountInfo<'info>,
+        creator_ata: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let cpi_accounts = Transfer {
+            from: ata.clone(),
+            to: creator_ata.clone(),
+            authority: self.user.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        transfer(CpiContext::new(cpi_program, cpi_accounts), 1)
+    }
+
+    pub fn output_cpi(&self) -> OutputCpi<'info> {
+        OutputCpi {
+            transmuter: self.transmuter.key(),
+            auth_bump: self.transmuter.auth_bump,
+            creator: self.creator.to_account_info(),
+            user: self.user.to_account_info(),
+            auth: self.auth.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            token_metadata_program: self.token_metadata_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            rent: self.rent.to_account_info(),
+            sysvar_instructions: self.sysvar_instructions.to_account_info(),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::structs::Transmuter;
+use crate::{InputCpi, VaultAuth, MPL_CORE_ID};
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use mpl_bubblegum::programs::{SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID};
+
+#[derive(Accounts)]
+#[instruction(seed: u64, vault_seed: u64)]
+pub struct VaultRefund<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    #[account(mut, constraint = *creator.to_account_info().key == transmuter.authority)]
+    pub creator: SystemAccount<'info>,
+    #[account(mut, constraint = *user.to_account_info().key == vault_auth.user)]
+    pub user: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"transmuter", transmuter.creator.as_ref(), seed.to_le_bytes().as_ref()],
+        bump = transmuter.transmuter_bump,
+    )]
+    pub transmuter: Box<Account<'info, Transmuter>>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"vaultAuth", transmuter.key().as_ref(), user.key.as_ref(), vault_seed.to_le_bytes().as_ref()],
+        bump = vault_auth.vault_auth_bump,
+    )]
+    pub vault_auth: Box<Account<'info, VaultAuth>>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    #[account(address = MPL_CORE_ID)]
+    /// CHECK: This is the Metaplex Core program, only needed for core inputs
+    pub core_program: Option<UncheckedAccount<'info>>,
+    #[account(address = mpl_bubblegum::ID)]
+    /// CHECK: This is the Bubblegum program, only needed for cnft inputs
+    pub bubblegum_program: Option<UncheckedAccount<'info>>,
+    #[account(address = SPL_NOOP_ID)]
+    /// CHECK: This is the SPL noop program, only needed for cnft inputs
+    pub log_wrapper: Option<UncheckedAccount<'info>>,
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    /// CHECK: This is the SPL account compression program, only needed for cnft inputs
+    pub compression_program: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> VaultRefund<'info> {
+    pub fn input_cpi(&self, recipient: AccountInfo<'info>, vault_seed: u64) -> InputCpi<'info> {
+        InputCpi {
+            transmuter: self.transmuter.key(),
+            vault_seed,
+            vault_auth_bump: self.vault_auth.vault_auth_bump,
+            vault_auth: self.vault_auth.to_account_info(),
+            user: self.user.to_account_info(),
+            payer: self.cranker.to_account_info(),
+            recipient,
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            core_program: self
+                .core_program
+                .as_ref()
+                .map(|program| program.to_account_info()),
+            bubblegum_program: self
+                .bubblegum_program
+                .as_ref()
+                .map(|program| program.to_account_info()),
+            log_wrapper: self
+                .log_wrapper
+                .as_ref()
+                .map(|program| program.to_account_info()),
+            compression_program: self
+                .compression_program
+                .as_ref()
+                .map(|program| program.to_account_info()),
+        }
+    }
+}
@@ -62,4 +62,68 @@ pub enum TransmuterError {
     RuleNotApplied,
     #[msg("Missing trait in transmuter")]
     MissingTrait,
+    #[msg("Invalid Metaplex Core asset")]
+    InvalidCoreAsset,
+    #[msg("Invalid collection provided for output")]
+    InvalidCollection,
+    #[msg("Invalid merkle tree provided for output")]
+    InvalidMerkleTree,
+    #[msg("Invalid master edition provided for print output")]
+    InvalidMasterEdition,
+    #[msg("Master edition supply lower than transmute max")]
+    PrintSupplyExceeded,
+    #[msg("Invalid pool provided for output")]
+    InvalidPool,
+    #[msg("Output pool is depleted")]
+    PoolDepleted,
+    #[msg("Invalid mint provided for output")]
+    InvalidOutputMint,
+    #[msg("Output supply cap reached")]
+    SupplyCapReached,
+    #[msg("Invalid escrow provided for output")]
+    InvalidEscrow,
+    #[msg("Escrow balance too low for output")]
+    InsufficientEscrow,
+    #[msg("Metadata account not derived from the mint")]
+    InvalidMetadata,
+    #[msg("Edition account not derived from the mint")]
+    InvalidEdition,
+    #[msg("Vault is not the vault authority token account for the mint")]
+    InvalidVault,
+    #[msg("Token account not owned by the user for the mint")]
+    InvalidUserAta,
+    #[msg("Token account not owned by the creator for the mint")]
+    InvalidCreatorAta,
+    #[msg("Invalid token metadata program")]
+    InvalidTokenMetadataProgram,
+    #[msg("Input uris do not match the vault")]
+    InvalidInputUris,
+    #[msg("Vault has not expired")]
+    VaultNotExpired,
+    #[msg("Only the creator can resolve inputs")]
+    CreatorResolveOnly,
+    #[msg("Recipe inputs changed, abort the vault to recover them")]
+    InputsChanged,
+    #[msg("Recipe changes are timelocked")]
+    RecipeTimelocked,
+    #[msg("No pending recipe change")]
+    NoPendingRecipe,
+    #[msg("Transmuter is published and can't be changed")]
+    TransmuterImmutable,
+    #[msg("Transmuter config is frozen")]
+    ConfigFrozen,
+    #[msg("Transmuter still has open vaults")]
+    VaultsOpen,
+    #[msg("Signer is not the pending authority")]
+    InvalidPendingAuthority,
+    #[msg("Signer is missing the operator role")]
+    MissingRole,
+    #[msg("Only the transmuter authority can do this")]
+    NotAuthority,
+    #[msg("Too many operators")]
+    TooManyOperators,
+    #[msg("Only nft inputs can be sent in a single transmute")]
+    UnsupportedInput,
+    #[msg("Only minted nft and spl outputs can be claimed in a batch")]
+    UnsupportedOutput,
 }
@@ -1,8 +1,14 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
 use anchor_spl::token::{burn, transfer, Burn, TokenAccount, Transfer};
 
-use mpl_token_metadata::accounts::Metadata;
+use mpl_bubblegum::accounts::TreeConfig;
+use mpl_bubblegum::hash::{hash_creators, hash_metadata};
+use mpl_bubblegum::types::MetadataArgs;
+use mpl_bubblegum::utils::get_asset_id;
+use mpl_token_metadata::accounts::{MasterEdition, Metadata};
 
+use std::str::FromStr;
 use url::Url;
 
 mod contexts;
@@ -49,14 +55,18 @@ pub mod transmuter {
         let transmuter = &mut ctx.accounts.transmuter;
         transmuter.seed = seed;
         transmuter.creator = ctx.accounts.creator.as_ref().key();
+        transmuter.authority = ctx.accounts.creator.as_ref().key();
         transmuter.auth_bump = ctx.bumps.auth;
         transmuter.transmuter_bump = ctx.bumps.transmuter;
         transmuter.transmute_count = 0;
         transmuter.locked = true;
 
-        let transmuter_config: Config = parse_json::<Config>(&config_json).unwrap();
+        let transmuter_config: Config = parse_json::<Config>(&config_json)?;
         transmuter.transmute_max = transmuter_config.transmute_max;
         transmuter.traits_uri = transmuter_config.traits_uri;
+        transmuter.vault_ttl_seconds = transmuter_config.vault_ttl_seconds;
+        transmuter.creator_resolve_only = transmuter_config.creator_resolve_only;
+        transmuter.recipe_timelock_seconds = transmuter_config.recipe_timelock_seconds;
 
         Ok(())
     }
@@ -90,14 +100,18 @@ pub mod transmuter {
         let transmuter = &mut ctx.accounts.transmuter;
         transmuter.seed = seed;
         transmuter.creator = ctx.accounts.creator.as_ref().key();
+        transmuter.authority = ctx.accounts.creator.as_ref().key();
         transmuter.auth_bump = ctx.bumps.auth;
         transmuter.transmuter_bump = ctx.bumps.transmuter;
         transmuter.transmute_count = 0;
         transmuter.locked = true;
 
-        let transmuter_config: Config = parse_json::<Config>(&config_json).unwrap();
+        let transmuter_config: Config = parse_json::<Config>(&config_json)?;
         transmuter.transmute_max = transmuter_config.transmute_max;
         transmuter.traits_uri = transmuter_config.traits_uri;
+        transmuter.vault_ttl_seconds = transmuter_config.vault_ttl_seconds;
+        transmuter.creator_resolve_only = transmuter_config.creator_resolve_only;
+        transmuter.recipe_timelock_seconds = transmuter_config.recipe_timelock_seconds;
 
         Ok(())
     }
@@ -108,12 +122,27 @@ pub mod transmuter {
         config_json: String,
     ) -> Result<()> {
         let transmuter = &mut ctx.accounts.transmuter;
-        let transmuter_config: Config = parse_json::<Config>(&config_json).unwrap();
-        transmuter.transmute_max = transmuter_config.transmute_max;
-        transmuter.traits_uri = transmuter_config.traits_uri;
+        transmuter.check_role(ctx.accounts.creator.key, ROLE_EDIT)?;
+        require!(!transmuter.config_frozen, TransmuterError::ConfigFrozen);
+
+        let transmuter_config: Config = parse_json::<Config>(&config_json)?;
+        let config_update = ConfigUpdate {
+            transmute_max: transmuter_config.transmute_max,
+            traits_uri: transmuter_config.traits_uri,
+            vault_ttl_seconds: transmuter_config.vault_ttl_seconds,
+            creator_resolve_only: transmuter_config.creator_resolve_only,
+        };
+
+        //Published transmuters only keep transmute_max adjustable, other fields must be unchanged
+        if transmuter.immutable {
+            let is_frozen_unchanged = config_update.traits_uri == transmuter.traits_uri
+                && config_update.vault_ttl_seconds == transmuter.vault_ttl_seconds
+                && config_update.creator_resolve_only == transmuter.creator_resolve_only;
+            require!(is_frozen_unchanged, TransmuterError::TransmuterImmutable);
+        }
 
         //TODO: update transmuter size from input/output length
-        Ok(())
+        transmuter.set_config(config_update)
     }
 
     pub fn transmuter_set_input(
@@ -122,8 +151,8 @@ pub mod transmuter {
         input_json: String,
     ) -> Result<()> {
         let transmuter = &mut ctx.accounts.transmuter;
-        transmuter.inputs.push(input_json);
-        Ok(())
+        transmuter.check_role(ctx.accounts.creator.key, ROLE_EDIT)?;
+        transmuter.push_input(input_json)
     }
 
     pub fn transmuter_set_output(
@@ -132,13 +161,29 @@ pub mod transmuter {
         output_json: String,
     ) -> Result<()> {
         let transmuter = &mut ctx.accounts.transmuter;
+        transmuter.check_role(ctx.accounts.creator.key, ROLE_EDIT)?;
 
         let output_info = parse_json::<OutputInfo>(&output_json)?;
-        let is_spl = output_info.token_standard == "spl";
-        require!(!is_spl, TransmuterError::InvalidMethod);
+        let is_escrowed =
+            output_info.token_standard == "spl" || output_info.token_standard == "sol";
+        require!(!is_escrowed, TransmuterError::InvalidMethod);
+
+        //Print and pool outputs need their tokens deposited
+        let is_deposited = output_info.method == "print" || output_info.method == "pool";
+        require!(!is_deposited, TransmuterError::InvalidMethod);
+
+        if output_info.token_standard == "cnft" {
+            require!(
+                output_info.merkle_tree.is_some(),
+                TransmuterError::InvalidMerkleTree
+            );
+            require!(
+                output_info.collection.is_some(),
+                TransmuterError::InvalidCollection
+            );
+        }
 
-        transmuter.outputs.push(output_json);
-        Ok(())
+        transmuter.push_output(output_json)
     }
 
     pub fn transmuter_set_output_spl(
@@ -150,12 +195,174 @@ pub mod transmuter {
         let transmuter = &mut ctx.accounts.transmuter;
 
         let output_info = parse_json::<OutputInfo>(&output_json)?;
-        let is_spl = output_info.token_standard == "spl";
+        let is_spl = output_info.token_standard == "spl" && output_info.method != "mint";
         require!(is_spl, TransmuterError::InvalidMethod);
         require!(amount > 0, TransmuterError::InvalidAmount);
 
-        ctx.accounts.transfer_to_auth(&amount);
-        ctx.accounts.transmuter.outputs.push(output_json);
+        //Each escrowed output owns the auth ata of its mint
+        let mint_key = ctx.accounts.mint.key().to_string();
+        require!(
+            output_info.mint == Some(mint_key.clone()),
+            TransmuterError::InvalidOutputMint
+        );
+        let transmuter_outputs = transmuter.escrowed_outputs()?;
+        let is_mint_escrowed = transmuter_outputs.iter().any(|output_info| {
+            output_info.token_standard == "spl"
+                && output_info.method != "mint"
+                && output_info.mint == Some(mint_key.clone())
+        });
+        require!(!is_mint_escrowed, TransmuterError::InvalidOutputMint);
+
+        ctx.accounts.transfer_to_auth(&amount)?;
+        ctx.accounts.transmuter.push_output(output_json)
+    }
+
+    pub fn transmuter_set_output_spl_mint(
+        ctx: Context<TransmuterSetSplMint>,
+        _seed: u64,
+        output_json: String,
+    ) -> Result<()> {
+        let output_info = parse_json::<OutputInfo>(&output_json)?;
+        let is_spl_mint = output_info.token_standard == "spl" && output_info.method == "mint";
+        require!(is_spl_mint, TransmuterError::InvalidMethod);
+        require!(output_info.amount > 0, TransmuterError::InvalidAmount);
+        require!(
+            output_info.mint == Some(ctx.accounts.mint.key().to_string()),
+            TransmuterError::InvalidOutputMint
+        );
+
+        ctx.accounts.set_auth_as_authority()?;
+        ctx.accounts.transmuter.push_output(output_json)
+    }
+
+    //Outputs are removed with transmuter_remove_output, this only hands the mint back
+    pub fn transmuter_revoke_output_spl_mint(
+        ctx: Context<TransmuterRevokeSplMint>,
+        _seed: u64,
+    ) -> Result<()> {
+        let mint_key = ctx.accounts.mint.key().to_string();
+        let is_mint_escrowed =
+            ctx.accounts
+                .transmuter
+                .escrowed_outputs()?
+                .iter()
+                .any(|output_info| {
+                    output_info.token_standard == "spl"
+                        && output_info.method == "mint"
+                        && output_info.mint == Some(mint_key.clone())
+                });
+        ctx.accounts.transmuter.check_escrows_unpinned()?;
+        ctx.accounts
+            .transmuter
+            .check_escrow_withdrawable(is_mint_escrowed)?;
+
+        ctx.accounts.set_creator_as_authority()
+    }
+
+    pub fn transmuter_set_output_print(
+        ctx: Context<TransmuterSetPrint>,
+        _seed: u64,
+        output_json: String,
+    ) -> Result<()> {
+        let transmuter = &ctx.accounts.transmuter;
+
+        let output_info = parse_json::<OutputInfo>(&output_json)?;
+        let is_print = output_info.token_standard == "nft" && output_info.method == "print";
+        require!(is_print, TransmuterError::InvalidMethod);
+        require!(
+            output_info.mint == Some(ctx.accounts.master_mint.key().to_string()),
+            TransmuterError::InvalidMasterEdition
+        );
+
+        //Remaining prints must cover every transmutation left
+        let master_edition =
+            MasterEdition::try_from(&ctx.accounts.master_edition.to_account_info())?;
+        if let Some(max_supply) = master_edition.max_supply {
+            let remaining_prints = max_supply.saturating_sub(master_edition.supply);
+            let is_supply_enough = transmuter.transmute_max.is_some_and(|transmute_max| {
+                transmute_max.saturating_sub(transmuter.transmute_count) <= remaining_prints
+            });
+            require!(is_supply_enough, TransmuterError::PrintSupplyExceeded);
+        }
+
+        ctx.accounts.transfer_to_auth()?;
+        ctx.accounts.transmuter.push_output(output_json)
+    }
+
+    pub fn transmuter_set_output_pool(
+        ctx: Context<TransmuterSetPool>,
+        _seed: u64,
+        pool_seed: u64,
+        shuffle: bool,
+        output_json: String,
+    ) -> Result<()> {
+        let output_info = parse_json::<OutputInfo>(&output_json)?;
+        let is_pool = output_info.token_standard == "nft" && output_info.method == "pool";
+        require!(is_pool, TransmuterError::InvalidMethod);
+        require!(
+            output_info.pool == Some(ctx.accounts.pool.key().to_string()),
+            TransmuterError::InvalidPool
+        );
+
+        let pool = &mut ctx.accounts.pool;
+        pool.transmuter = ctx.accounts.transmuter.key();
+        pool.seed = pool_seed;
+        pool.shuffle = shuffle;
+        pool.pool_bump = ctx.bumps.pool;
+        pool.reserved = 0;
+        ctx.accounts.transmuter.pool_count += 1;
+
+        ctx.accounts.transmuter.push_output(output_json)
+    }
+
+    pub fn transmuter_add_to_pool(
+        ctx: Context<TransmuterAddPool>,
+        _seed: u64,
+        _pool_seed: u64,
+    ) -> Result<()> {
+        let mint_key = ctx.accounts.mint.key();
+        require!(
+            !ctx.accounts.pool.mints.contains(&mint_key),
+            TransmuterError::InvalidPool
+        );
+
+        ctx.accounts.transfer_to_auth()?;
+        ctx.accounts.pool.mints.push(mint_key);
+        Ok(())
+    }
+
+    pub fn transmuter_withdraw_pool(
+        ctx: Context<TransmuterWithdrawPool>,
+        _seed: u64,
+        _pool_seed: u64,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key().to_string();
+        let is_pool_escrowed = ctx
+            .accounts
+            .transmuter
+            .escrowed_outputs()?
+            .iter()
+            .any(|output_info| output_info.pool == Some(pool_key.clone()));
+        ctx.accounts
+            .transmuter
+            .check_escrow_withdrawable(is_pool_escrowed)?;
+
+        require!(
+            ctx.accounts.pool.available() > 0,
+            TransmuterError::PoolDepleted
+        );
+
+        let mint_key = ctx.accounts.mint.key();
+        let position = ctx
+            .accounts
+            .pool
+            .mints
+            .iter()
+            .position(|mint| *mint == mint_key)
+            .ok_or(TransmuterError::InvalidPool)?;
+
+        ctx.accounts.transfer_from_auth()?;
+        ctx.accounts.pool.mints.remove(position);
         Ok(())
     }
 
@@ -168,50 +375,349 @@ pub mod transmuter {
 
         require!(amount > 0, TransmuterError::InvalidAmount);
 
-        ctx.accounts.transfer_to_auth(&amount);
+        let mint_key = ctx.accounts.mint.key().to_string();
+        let transmuter_outputs = transmuter.escrowed_outputs()?;
+        let is_mint_escrowed = transmuter_outputs.iter().any(|output_info| {
+            output_info.token_standard == "spl"
+                && output_info.method != "mint"
+                && output_info.mint == Some(mint_key.clone())
+        });
+        require!(is_mint_escrowed, TransmuterError::InvalidOutputMint);
+
+        ctx.accounts.transfer_to_auth(&amount)?;
         Ok(())
     }
 
-    pub fn transmuter_cancel_output_spl(
-        ctx: Context<TransmuterCancelSpl>,
+    //Outputs are removed with transmuter_remove_output, this only drains the escrow
+    pub fn transmuter_cancel_output_spl<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TransmuterCancelSpl<'info>>,
         _seed: u64,
-        index: u64,
     ) -> Result<()> {
-        let transmuter = &mut ctx.accounts.transmuter;
+        //Pool items only leave through transmuter_withdraw_pool, every pool is passed as remaining accounts
+        let is_pooled = is_pool_mint(
+            ctx.accounts.mint.key(),
+            ctx.accounts.transmuter.key(),
+            ctx.accounts.transmuter.pool_count,
+            ctx.remaining_accounts,
+        )?;
+        require!(!is_pooled, TransmuterError::InvalidPool);
+
+        let mint_key = ctx.accounts.mint.key().to_string();
+        let is_mint_escrowed =
+            ctx.accounts
+                .transmuter
+                .escrowed_outputs()?
+                .iter()
+                .any(|output_info| {
+                    let is_spl =
+                        output_info.token_standard == "spl" && output_info.method != "mint";
+                    let is_print = output_info.method == "print";
+                    (is_spl || is_print) && output_info.mint == Some(mint_key.clone())
+                });
+        ctx.accounts.transmuter.check_escrows_unpinned()?;
+        ctx.accounts
+            .transmuter
+            .check_escrow_withdrawable(is_mint_escrowed)?;
+
+        ctx.accounts
+            .transfer_from_auth(&ctx.accounts.auth_ata.amount)
+    }
 
+    pub fn transmuter_set_output_sol(
+        ctx: Context<TransmuterSetSol>,
+        _seed: u64,
+        escrow_seed: u64,
+        output_json: String,
+        amount: u64,
+    ) -> Result<()> {
+        let output_info = parse_json::<OutputInfo>(&output_json)?;
+        let is_sol = output_info.token_standard == "sol";
+        require!(is_sol, TransmuterError::InvalidMethod);
+        require!(output_info.amount > 0, TransmuterError::InvalidAmount);
         require!(
-            index >= 0 && transmuter.outputs.len() > (index as usize),
-            TransmuterError::InvalidIndex
+            output_info.escrow == Some(ctx.accounts.escrow.key().to_string()),
+            TransmuterError::InvalidEscrow
         );
 
-        let output_json = &transmuter.outputs[index as usize];
-        let output_info = parse_json::<OutputInfo>(output_json)?;
-        let is_spl = output_info.token_standard == "spl";
-        require!(is_spl, TransmuterError::InvalidMethod);
+        //One escrow per output
+        let escrow_key = ctx.accounts.escrow.key();
+        let transmuter_outputs = ctx.accounts.transmuter.escrowed_outputs()?;
+        let is_escrow_used = transmuter_outputs
+            .iter()
+            .any(|output_info| output_info.escrow == Some(escrow_key.to_string()));
+        require!(!is_escrow_used, TransmuterError::InvalidEscrow);
+
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.transmuter = ctx.accounts.transmuter.key();
+        escrow.seed = escrow_seed;
+        escrow.escrow_bump = ctx.bumps.escrow;
+
+        if amount > 0 {
+            ctx.accounts.transfer_to_escrow(amount)?;
+        }
+        ctx.accounts.transmuter.push_output(output_json)
+    }
+
+    pub fn transmuter_add_output_sol(
+        ctx: Context<TransmuterSetSol>,
+        _seed: u64,
+        _escrow_seed: u64,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, TransmuterError::InvalidAmount);
+
+        let escrow_key = ctx.accounts.escrow.key().to_string();
+        let transmuter_outputs = ctx.accounts.transmuter.escrowed_outputs()?;
+        let is_escrow_set = transmuter_outputs
+            .iter()
+            .any(|output_info| output_info.escrow == Some(escrow_key.clone()));
+        require!(is_escrow_set, TransmuterError::InvalidEscrow);
+
+        ctx.accounts.transfer_to_escrow(amount)
+    }
+
+    //Outputs are removed with transmuter_remove_output, this only closes the escrow
+    pub fn transmuter_cancel_output_sol(
+        ctx: Context<TransmuterCancelSol>,
+        _seed: u64,
+    ) -> Result<()> {
+        let escrow_key = ctx.accounts.escrow.key().to_string();
+        let is_escrow_used = ctx
+            .accounts
+            .transmuter
+            .escrowed_outputs()?
+            .iter()
+            .any(|output_info| output_info.escrow == Some(escrow_key.clone()));
 
+        //Escrow is closed to the authority with its remaining balance
+        ctx.accounts.transmuter.check_escrows_unpinned()?;
         ctx.accounts
-            .transfer_from_auth(&ctx.accounts.auth_ata.amount);
-        ctx.accounts.transmuter.outputs.remove(index as usize);
+            .transmuter
+            .check_escrow_withdrawable(is_escrow_used)
+    }
+
+    pub fn transmuter_remove_output(
+        ctx: Context<TransmuterSet>,
+        _seed: u64,
+        index: u64,
+    ) -> Result<()> {
+        let transmuter = &mut ctx.accounts.transmuter;
+        transmuter.check_role(ctx.accounts.creator.key, ROLE_EDIT)?;
+        transmuter.remove_output(index as usize)?;
         Ok(())
     }
 
     pub fn transmuter_pause(ctx: Context<TransmuterSet>, _seed: u64) -> Result<()> {
-        ctx.accounts.transmuter.locked = true;
+        let transmuter = &mut ctx.accounts.transmuter;
+        transmuter.check_role(ctx.accounts.creator.key, ROLE_PAUSE)?;
+        transmuter.locked = true;
         Ok(())
     }
 
     pub fn transmuter_resume(ctx: Context<TransmuterSet>, _seed: u64) -> Result<()> {
-        ctx.accounts.transmuter.locked = false;
+        let transmuter = &mut ctx.accounts.transmuter;
+        transmuter.check_role(ctx.accounts.creator.key, ROLE_PAUSE)?;
+        transmuter.locked = false;
+        Ok(())
+    }
+
+    pub fn transmuter_apply_recipe(ctx: Context<TransmuterSet>, _seed: u64) -> Result<()> {
+        let transmuter = &mut ctx.accounts.transmuter;
+        transmuter.check_role(ctx.accounts.creator.key, ROLE_EDIT)?;
+        let now = Clock::get()?.unix_timestamp;
+        transmuter.apply_recipe(now)
+    }
+
+    pub fn transmuter_cancel_recipe(ctx: Context<TransmuterSet>, _seed: u64) -> Result<()> {
+        let transmuter = &mut ctx.accounts.transmuter;
+        transmuter.check_role(ctx.accounts.creator.key, ROLE_EDIT)?;
+        transmuter.cancel_recipe()
+    }
+
+    pub fn transmuter_publish(
+        ctx: Context<TransmuterSet>,
+        _seed: u64,
+        freeze_config: bool,
+    ) -> Result<()> {
+        let transmuter = &mut ctx.accounts.transmuter;
+        transmuter.check_authority(ctx.accounts.creator.key)?;
+        require!(!transmuter.immutable, TransmuterError::TransmuterImmutable);
+        require!(!transmuter.inputs.is_empty(), TransmuterError::InputsNotSet);
+        require!(
+            !transmuter.outputs.is_empty(),
+            TransmuterError::OutputsNotSet
+        );
+        require!(
+            transmuter.pending_effective_at.is_none(),
+            TransmuterError::RecipeTimelocked
+        );
+
+        transmuter.immutable = true;
+        transmuter.config_frozen = freeze_config;
+        Ok(())
+    }
+
+    //Ownership moves in two steps, the creator seed never changes
+    pub fn transmuter_propose_authority(
+        ctx: Context<TransmuterSet>,
+        _seed: u64,
+        new_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        let transmuter = &mut ctx.accounts.transmuter;
+        transmuter.check_authority(ctx.accounts.creator.key)?;
+        transmuter.pending_authority = new_authority;
+        Ok(())
+    }
+
+    //Operators act with a subset of the authority roles
+    pub fn transmuter_set_operator(
+        ctx: Context<TransmuterSet>,
+        _seed: u64,
+        operator: Pubkey,
+        roles: u8,
+    ) -> Result<()> {
+        let transmuter = &mut ctx.accounts.transmuter;
+        transmuter.check_authority(ctx.accounts.creator.key)?;
+        transmuter.set_operator(operator, roles)
+    }
+
+    pub fn transmuter_accept_authority(
+        ctx: Context<TransmuterAcceptAuthority>,
+        _seed: u64,
+    ) -> Result<()> {
+        let transmuter = &mut ctx.accounts.transmuter;
+        transmuter.authority = ctx.accounts.authority.key();
+        transmuter.pending_authority = None;
         Ok(())
     }
 
-    pub fn transmuter_close(_ctx: Context<TransmuterClose>) -> Result<()> {
+    pub fn transmuter_close<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TransmuterClose<'info>>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.transmuter.open_vaults == 0,
+            TransmuterError::VaultsOpen
+        );
+
+        //Every auth owned escrow goes back to the creator, accounts are passed once per escrow
+        //in the order it first appears in the live then pending outputs
+        let transmuter_outputs = ctx.accounts.transmuter.escrowed_outputs()?;
+        let mut escrow_accounts = ctx.remaining_accounts.iter();
+        let mut drained_escrows: Vec<Pubkey> = Vec::new();
+        let mut closed_pools: u64 = 0;
+        for output_info in transmuter_outputs.iter() {
+            match (
+                output_info.token_standard.as_str(),
+                output_info.method.as_str(),
+            ) {
+                ("spl", "mint") => {
+                    let mint_key = output_info
+                        .mint
+                        .as_ref()
+                        .and_then(|mint| Pubkey::from_str(mint).ok())
+                        .ok_or(TransmuterError::InvalidOutputMint)?;
+                    if drained_escrows.contains(&mint_key) {
+                        continue;
+                    }
+
+                    let mint = escrow_accounts
+                        .next()
+                        .ok_or(TransmuterError::InvalidOutputMint)?;
+                    require!(mint.key() == mint_key, TransmuterError::InvalidOutputMint);
+                    ctx.accounts.set_creator_as_authority(mint)?;
+                    drained_escrows.push(mint_key);
+                }
+                ("spl", _) | ("nft", "print") => {
+                    let mint_key = output_info
+                        .mint
+                        .as_ref()
+                        .and_then(|mint| Pubkey::from_str(mint).ok())
+                        .ok_or(TransmuterError::InvalidOutputMint)?;
+                    if drained_escrows.contains(&mint_key) {
+                        continue;
+                    }
+
+                    let (Some(auth_ata), Some(creator_ata)) =
+                        (escrow_accounts.next(), escrow_accounts.next())
+                    else {
+                        return err!(TransmuterError::InvalidEscrow);
+                    };
+                    ctx.accounts
+                        .drain_auth_ata(&mint_key, auth_ata, creator_ata)?;
+                    drained_escrows.push(mint_key);
+                }
+                ("nft", "pool") => {
+                    let pool_key = output_info
+                        .pool
+                        .as_ref()
+                        .and_then(|pool| Pubkey::from_str(pool).ok())
+                        .ok_or(TransmuterError::InvalidPool)?;
+                    if drained_escrows.contains(&pool_key) {
+                        continue;
+                    }
+
+                    let pool_account =
+                        escrow_accounts.next().ok_or(TransmuterError::InvalidPool)?;
+                    require!(pool_account.key() == pool_key, TransmuterError::InvalidPool);
+                    ctx.accounts
+                        .close_pool(pool_account, &mut escrow_accounts)?;
+                    closed_pools += 1;
+                    drained_escrows.push(pool_key);
+                }
+                ("sol", _) => {
+                    let escrow_key = output_info
+                        .escrow
+                        .as_ref()
+                        .and_then(|escrow| Pubkey::from_str(escrow).ok())
+                        .ok_or(TransmuterError::InvalidEscrow)?;
+                    if drained_escrows.contains(&escrow_key) {
+                        continue;
+                    }
+
+                    let escrow_account = escrow_accounts
+                        .next()
+                        .ok_or(TransmuterError::InvalidEscrow)?;
+                    require!(
+                        escrow_account.key() == escrow_key,
+                        TransmuterError::InvalidEscrow
+                    );
+                    ctx.accounts.close_sol_escrow(escrow_account)?;
+                    drained_escrows.push(escrow_key);
+                }
+                _ => {}
+            }
+        }
+
+        //Escrows left behind by removed outputs follow, auth atas come with a creator ata
+        while let Some(escrow_account) = escrow_accounts.next() {
+            if *escrow_account.owner == anchor_spl::token::ID {
+                let creator_ata = escrow_accounts
+                    .next()
+                    .ok_or(TransmuterError::InvalidEscrow)?;
+                let mint_key = Account::<TokenAccount>::try_from(escrow_account)?.mint;
+                ctx.accounts
+                    .drain_auth_ata(&mint_key, escrow_account, creator_ata)?;
+            } else if Account::<SolEscrow>::try_from(escrow_account).is_ok() {
+                ctx.accounts.close_sol_escrow(escrow_account)?;
+            } else {
+                ctx.accounts
+                    .close_pool(escrow_account, &mut escrow_accounts)?;
+                closed_pools += 1;
+            }
+        }
+
+        //Pool items are only tracked by their pool, so none can be left behind
+        require!(
+            closed_pools == ctx.accounts.transmuter.pool_count,
+            TransmuterError::InvalidPool
+        );
+
         Ok(())
     }
 
     // User methods
     pub fn user_init_vault_auth<'info>(
-        ctx: Context<UserInitVaultAuth>,
+        ctx: Context<'_, '_, 'info, 'info, UserInitVaultAuth<'info>>,
         _seed: u64,
         vault_seed: u64,
     ) -> Result<()> {
@@ -227,24 +733,44 @@ pub mod transmuter {
         require!(transmuter_inputs.len() > 0, TransmuterError::InputsNotSet);
         require!(transmuter_outputs.len() > 0, TransmuterError::OutputsNotSet);
 
+        //Pool outputs are passed as remaining accounts, with the slot hashes sysvar for shuffled ones
+        let is_shuffled = reserve_pool_items(
+            &transmuter_outputs,
+            transmuter.key(),
+            ctx.remaining_accounts,
+        )?;
+        if is_shuffled {
+            ctx.accounts.vault_auth.pick_seed =
+                commit_pick_seed(ctx.remaining_accounts, ctx.accounts.vault_auth.key())?;
+        }
+
         ctx.accounts.vault_auth.vault_auth_bump = ctx.bumps.vault_auth;
 
         // Vault auth info
         ctx.accounts.vault_auth.transmuter = transmuter.key();
         ctx.accounts.vault_auth.user = ctx.accounts.user.key();
         ctx.accounts.vault_auth.seed = vault_seed;
+        ctx.accounts.vault_auth.created_at = Clock::get()?.unix_timestamp;
 
         //Init locks
         ctx.accounts.vault_auth.user_locked = false;
         ctx.accounts.vault_auth.creator_locked = true;
 
+        //Pin the recipe the vault starts with
+        ctx.accounts.vault_auth.recipe_version = transmuter.recipe_version;
+        ctx.accounts.vault_auth.recipe_hash = transmuter.recipe_hash;
+        ctx.accounts.vault_auth.inputs = transmuter.inputs.clone();
+        ctx.accounts.vault_auth.outputs = transmuter.outputs.clone();
+
         //Init trackers
         ctx.accounts.vault_auth.handled_inputs =
             (0..transmuter_inputs.len()).map(|_| None).collect();
-        ctx.accounts.vault_auth.input_uris = (0..transmuter_inputs.len()).map(|_| None).collect();
+        ctx.accounts.vault_auth.input_hashes = (0..transmuter_inputs.len()).map(|_| None).collect();
         ctx.accounts.vault_auth.handled_outputs =
             (0..transmuter_outputs.len()).map(|_| None).collect();
 
+        ctx.accounts.transmuter.open_vaults += 1;
+
         Ok(())
     }
 
@@ -252,6 +778,7 @@ pub mod transmuter {
         ctx: Context<UserSendInput>,
         _seed: u64,
         vault_seed: u64,
+        slot_index: Option<u64>,
     ) -> Result<()> {
         let transmuter = &ctx.accounts.transmuter;
         require!(!transmuter.locked, TransmuterError::IsLocked);
@@ -264,75 +791,882 @@ pub mod transmuter {
             && transmuter.transmute_count >= transmuter.transmute_max.unwrap();
         require!(!is_max_reached, TransmuterError::MaxReached);
 
-        let transmuter_inputs = parse_json_vec::<InputInfo>(&transmuter.inputs)?;
+        let transmuter_inputs = parse_json_vec::<InputInfo>(&ctx.accounts.vault_auth.inputs)?;
 
-        //Find an input_info match
-        let mut is_match = false;
-        for index in 0..transmuter_inputs.len() {
-            if ctx.accounts.vault_auth.handled_inputs.len() > 0
-                && ctx.accounts.vault_auth.handled_inputs[index].is_some()
-            {
-                msg!("Index {:?} already exist in vault_auth", index);
-                continue;
+        let mint_key = ctx.accounts.mint.key();
+        require!(
+            !is_mint_handled(&ctx.accounts.vault_auth, mint_key),
+            TransmuterError::DuplicateInputAccount
+        );
+
+        let metadata = ctx.accounts.metadata.to_account_info();
+        let input_index = match slot_index {
+            //Requested slot must be free and match
+            Some(slot_index) => {
+                let index = slot_index as usize;
+                require!(
+                    index < transmuter_inputs.len()
+                        && ctx.accounts.vault_auth.handled_inputs[index].is_none(),
+                    TransmuterError::InvalidIndex
+                );
+                require!(
+                    is_matching_nft(&metadata, &transmuter_inputs[index])?,
+                    TransmuterError::InvalidInputAccount
+                );
+                index
+            }
+            //Otherwise take the first free slot matching
+            None => {
+                let mut first_match = None;
+                for (index, input_info) in transmuter_inputs.iter().enumerate() {
+                    if ctx.accounts.vault_auth.handled_inputs[index].is_some() {
+                        msg!("Index {:?} already exist in vault_auth", index);
+                        continue;
+                    }
+
+                    if is_matching_nft(&metadata, input_info)? {
+                        first_match = Some(index);
+                        break;
+                    }
+                }
+                first_match.ok_or(TransmuterError::InvalidInputAccount)?
             }
+        };
 
-            is_match = is_matching_nft(
-                &ctx.accounts.metadata.to_account_info(),
-                &transmuter_inputs[index],
-            )?;
+        ctx.accounts.vault_auth.handled_inputs[input_index] = Some(mint_key);
+        //TODO Maybe optional if split or merge
+        let input_metadata: Metadata = Metadata::try_from(&metadata)?;
+        ctx.accounts.vault_auth.input_hashes[input_index] = Some(hash_uri(&input_metadata.uri));
+
+        ctx.accounts.transfer_to_vault()?;
+
+        Ok(())
+    }
+
+    pub fn user_send_inputs<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UserSendInputs<'info>>,
+        _seed: u64,
+        _vault_seed: u64,
+    ) -> Result<()> {
+        let transmuter = &ctx.accounts.transmuter;
+        require!(!transmuter.locked, TransmuterError::IsLocked);
+        require!(
+            !&ctx.accounts.vault_auth.user_locked,
+            TransmuterError::UserLocked
+        );
+
+        let is_max_reached = transmuter.transmute_max.is_some()
+            && transmuter.transmute_count >= transmuter.transmute_max.unwrap();
+        require!(!is_max_reached, TransmuterError::MaxReached);
+
+        let transmuter_inputs = parse_json_vec::<InputInfo>(&ctx.accounts.vault_auth.inputs)?;
+
+        //Inputs come in (mint, ata, metadata, vault) groups
+        let input_groups = ctx.remaining_accounts.chunks_exact(4);
+        require!(
+            input_groups.len() > 0 && input_groups.remainder().is_empty(),
+            TransmuterError::InvalidInputAccount
+        );
+
+        for input_accounts in input_groups {
+            let [mint, ata, metadata, vault] = input_accounts else {
+                return err!(TransmuterError::InvalidInputAccount);
+            };
+
+            let user_ata = Account::<TokenAccount>::try_from(ata)?;
+            require!(
+                user_ata.mint == mint.key() && user_ata.owner == ctx.accounts.user.key(),
+                TransmuterError::InvalidUserAta
+            );
+            require!(
+                vault.key()
+                    == get_associated_token_address(&ctx.accounts.vault_auth.key(), &mint.key()),
+                TransmuterError::InvalidVault
+            );
+            let vault_ata = Account::<TokenAccount>::try_from(vault)?;
+            require!(
+                vault_ata.mint == mint.key() && vault_ata.owner == ctx.accounts.vault_auth.key(),
+                TransmuterError::InvalidVault
+            );
+            require!(
+                metadata.key() == Metadata::find_pda(&mint.key()).0,
+                TransmuterError::InvalidMetadata
+            );
+            require!(
+                !is_mint_handled(&ctx.accounts.vault_auth, mint.key()),
+                TransmuterError::DuplicateInputAccount
+            );
+
+            //Take the first free slot matching this input
+            let mut slot_index = None;
+            for (index, input_info) in transmuter_inputs.iter().enumerate() {
+                if ctx.accounts.vault_auth.handled_inputs[index].is_some() {
+                    continue;
+                }
 
-            if is_match {
-                ctx.accounts.vault_auth.handled_inputs[index] = Some(ctx.accounts.mint.key());
-                //TODO Maybe optional if split or merge
-                let input_metadata: Metadata =
-                    Metadata::try_from(&ctx.accounts.metadata.to_account_info())?;
-                ctx.accounts.vault_auth.input_uris[index] = Some(input_metadata.uri);
+                if is_matching_nft(metadata, input_info)? {
+                    slot_index = Some(index);
+                    break;
+                }
             }
+            let index = slot_index.ok_or(TransmuterError::InvalidInputAccount)?;
+
+            let input_metadata: Metadata = Metadata::try_from(metadata)?;
+            ctx.accounts.vault_auth.handled_inputs[index] = Some(mint.key());
+            ctx.accounts.vault_auth.input_hashes[index] = Some(hash_uri(&input_metadata.uri));
+
+            ctx.accounts.transfer_to_vault(ata, vault)?;
         }
 
-        require!(is_match, TransmuterError::InvalidInputAccount);
+        Ok(())
+    }
+
+    pub fn user_send_input_core(
+        ctx: Context<UserSendInputCore>,
+        _seed: u64,
+        _vault_seed: u64,
+        slot_index: Option<u64>,
+    ) -> Result<()> {
+        let transmuter = &ctx.accounts.transmuter;
+        require!(!transmuter.locked, TransmuterError::IsLocked);
+        require!(
+            !&ctx.accounts.vault_auth.user_locked,
+            TransmuterError::UserLocked
+        );
+
+        let is_max_reached = transmuter.transmute_max.is_some()
+            && transmuter.transmute_count >= transmuter.transmute_max.unwrap();
+        require!(!is_max_reached, TransmuterError::MaxReached);
+
+        let transmuter_inputs = parse_json_vec::<InputInfo>(&ctx.accounts.vault_auth.inputs)?;
+
+        let asset_key = ctx.accounts.asset.key();
+        require!(
+            !is_mint_handled(&ctx.accounts.vault_auth, asset_key),
+            TransmuterError::DuplicateInputAccount
+        );
+
+        let asset = ctx.accounts.asset.to_account_info();
+        let input_index = match slot_index {
+            //Requested slot must be free and match
+            Some(slot_index) => {
+                let index = slot_index as usize;
+                require!(
+                    index < transmuter_inputs.len()
+                        && ctx.accounts.vault_auth.handled_inputs[index].is_none(),
+                    TransmuterError::InvalidIndex
+                );
+                require!(
+                    is_matching_nft(&asset, &transmuter_inputs[index])?,
+                    TransmuterError::InvalidInputAccount
+                );
+                index
+            }
+            //Otherwise take the first free slot matching
+            None => {
+                let mut first_match = None;
+                for (index, input_info) in transmuter_inputs.iter().enumerate() {
+                    if ctx.accounts.vault_auth.handled_inputs[index].is_some() {
+                        msg!("Index {:?} already exist in vault_auth", index);
+                        continue;
+                    }
+
+                    if is_matching_nft(&asset, input_info)? {
+                        first_match = Some(index);
+                        break;
+                    }
+                }
+                first_match.ok_or(TransmuterError::InvalidInputAccount)?
+            }
+        };
+
+        let input_asset = CoreAsset::try_from(&asset)?;
+        ctx.accounts.vault_auth.handled_inputs[input_index] = Some(asset_key);
+        let input_uri = uri_from_traits(&input_asset.uri, input_asset.attributes);
+        ctx.accounts.vault_auth.input_hashes[input_index] = Some(hash_uri(&input_uri));
+
+        ctx.accounts.transfer_to_vault()?;
+        ctx.accounts.fund_resolve_tip()?;
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn user_send_input_cnft<'info>(
+        ctx: Context<'_, '_, '_, 'info, UserSendInputCnft<'info>>,
+        _seed: u64,
+        _vault_seed: u64,
+        root: [u8; 32],
+        nonce: u64,
+        leaf_index: u32,
+        metadata_json: String,
+        slot_index: Option<u64>,
+    ) -> Result<()> {
+        let transmuter = &ctx.accounts.transmuter;
+        require!(!transmuter.locked, TransmuterError::IsLocked);
+        require!(
+            !&ctx.accounts.vault_auth.user_locked,
+            TransmuterError::UserLocked
+        );
+
+        let is_max_reached = transmuter.transmute_max.is_some()
+            && transmuter.transmute_count >= transmuter.transmute_max.unwrap();
+        require!(!is_max_reached, TransmuterError::MaxReached);
+
+        let transmuter_inputs = parse_json_vec::<InputInfo>(&ctx.accounts.vault_auth.inputs)?;
+        let metadata = parse_json::<MetadataArgs>(&metadata_json)?;
+        let asset_id = get_asset_id(&ctx.accounts.merkle_tree.key(), nonce);
+        require!(
+            !is_mint_handled(&ctx.accounts.vault_auth, asset_id),
+            TransmuterError::DuplicateInputAccount
+        );
+
+        let input_index = match slot_index {
+            //Requested slot must be free and match
+            Some(slot_index) => {
+                let index = slot_index as usize;
+                require!(
+                    index < transmuter_inputs.len()
+                        && ctx.accounts.vault_auth.handled_inputs[index].is_none(),
+                    TransmuterError::InvalidIndex
+                );
+                require!(
+                    is_matching_cnft(&metadata, &transmuter_inputs[index]),
+                    TransmuterError::InvalidInputAccount
+                );
+                index
+            }
+            //Otherwise take the first free slot matching
+            None => {
+                let mut first_match = None;
+                for (index, input_info) in transmuter_inputs.iter().enumerate() {
+                    if ctx.accounts.vault_auth.handled_inputs[index].is_some() {
+                        msg!("Index {:?} already exist in vault_auth", index);
+                        continue;
+                    }
+
+                    if is_matching_cnft(&metadata, input_info) {
+                        first_match = Some(index);
+                        break;
+                    }
+                }
+                first_match.ok_or(TransmuterError::InvalidInputAccount)?
+            }
+        };
+
+        ctx.accounts.vault_auth.handled_inputs[input_index] = Some(asset_id);
+        ctx.accounts.vault_auth.input_hashes[input_index] = Some(hash_uri(&metadata.uri));
+
+        //Bubblegum rejects the transfer if the metadata does not hash to the leaf
+        let leaf_info = LeafInfo {
+            root,
+            data_hash: hash_metadata(&metadata)
+                .map_err(|_| TransmuterError::InvalidInputAccount)?,
+            creator_hash: hash_creators(&metadata.creators),
+            nonce,
+            index: leaf_index,
+        };
+        ctx.accounts
+            .transfer_to_vault(&leaf_info, ctx.remaining_accounts)?;
+        ctx.accounts.fund_resolve_tip()?;
+
+        Ok(())
+    }
+
+    pub fn user_cancel_input<'info>(
+        ctx: Context<UserCancelInput>,
+        _seed: u64,
+        vault_seed: u64,
+    ) -> Result<()> {
+        let transmuter = &ctx.accounts.transmuter;
+        let vault_auth = &ctx.accounts.vault_auth;
+
+        require!(!transmuter.locked, TransmuterError::IsLocked);
+        require!(!vault_auth.user_locked, TransmuterError::UserLocked);
+        require!(
+            is_mint_handled(vault_auth, ctx.accounts.mint.key()),
+            TransmuterError::InvalidInputAccount
+        );
+
+        ctx.accounts.transfer_from_vault(vault_seed)?;
+        ctx.accounts.close_vault(vault_seed)?;
+
+        let input_info_index = vault_auth
+            .handled_inputs
+            .iter()
+            .position(|&input: &Option<Pubkey>| input == Some(ctx.accounts.mint.key()))
+            .unwrap();
+        ctx.accounts.vault_auth.handled_inputs[input_info_index] = None;
+
+        Ok(())
+    }
+
+    pub fn user_cancel_input_core(
+        ctx: Context<UserCancelInputCore>,
+        _seed: u64,
+        vault_seed: u64,
+    ) -> Result<()> {
+        let transmuter = &ctx.accounts.transmuter;
+        let vault_auth = &ctx.accounts.vault_auth;
+
+        require!(!transmuter.locked, TransmuterError::IsLocked);
+        require!(!vault_auth.user_locked, TransmuterError::UserLocked);
+        require!(
+            is_mint_handled(vault_auth, ctx.accounts.asset.key()),
+            TransmuterError::InvalidInputAccount
+        );
+
+        ctx.accounts.transfer_from_vault(vault_seed)?;
+
+        let input_info_index = vault_auth
+            .handled_inputs
+            .iter()
+            .position(|&input: &Option<Pubkey>| input == Some(ctx.accounts.asset.key()))
+            .unwrap();
+        ctx.accounts.vault_auth.handled_inputs[input_info_index] = None;
+
+        Ok(())
+    }
+
+    pub fn user_cancel_input_cnft<'info>(
+        ctx: Context<'_, '_, '_, 'info, UserCancelInputCnft<'info>>,
+        _seed: u64,
+        vault_seed: u64,
+        leaf_info: LeafInfo,
+    ) -> Result<()> {
+        let transmuter = &ctx.accounts.transmuter;
+        let vault_auth = &ctx.accounts.vault_auth;
+        let asset_id = get_asset_id(&ctx.accounts.merkle_tree.key(), leaf_info.nonce);
+
+        require!(!transmuter.locked, TransmuterError::IsLocked);
+        require!(!vault_auth.user_locked, TransmuterError::UserLocked);
+        require!(
+            is_mint_handled(vault_auth, asset_id),
+            TransmuterError::InvalidInputAccount
+        );
+
+        ctx.accounts
+            .transfer_from_vault(vault_seed, &leaf_info, ctx.remaining_accounts)?;
+
+        let input_info_index = vault_auth
+            .handled_inputs
+            .iter()
+            .position(|&input: &Option<Pubkey>| input == Some(asset_id))
+            .unwrap();
+        ctx.accounts.vault_auth.handled_inputs[input_info_index] = None;
+
+        Ok(())
+    }
+
+    pub fn user_abort_vault<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UserAbortVault<'info>>,
+        _seed: u64,
+        vault_seed: u64,
+        leaves: Vec<VaultLeaf>,
+    ) -> Result<()> {
+        let vault_auth = &ctx.accounts.vault_auth;
+        require!(
+            no_outputs_handled(vault_auth),
+            TransmuterError::IsNotClaimable
+        );
+
+        let transmuter_inputs = parse_json_vec::<InputInfo>(&vault_auth.inputs)?;
+        ctx.accounts
+            .input_cpi(ctx.accounts.user.to_account_info(), vault_seed)
+            .return_inputs(
+                &transmuter_inputs,
+                &vault_auth.handled_inputs,
+                ctx.remaining_accounts,
+                &leaves,
+                false,
+            )?;
+
+        //Pools follow the input accounts
+        let transmuter_outputs = parse_json_vec::<OutputInfo>(&vault_auth.outputs)?;
+        release_pool_items(
+            &transmuter_outputs,
+            &vault_auth.handled_outputs,
+            ctx.accounts.transmuter.key(),
+            ctx.remaining_accounts,
+        )?;
+
+        //Vault auth is closed to the user once empty
+        let input_count = ctx.accounts.vault_auth.handled_inputs.len();
+        ctx.accounts.vault_auth.handled_inputs = vec![None; input_count];
+        ctx.accounts.transmuter.open_vaults = ctx.accounts.transmuter.open_vaults.saturating_sub(1);
+
+        Ok(())
+    }
+
+    pub fn user_sync_vault<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UserSyncVault<'info>>,
+        _seed: u64,
+        _vault_seed: u64,
+    ) -> Result<()> {
+        let transmuter = &ctx.accounts.transmuter;
+        let vault_auth = &ctx.accounts.vault_auth;
+        //Partially claimed vaults finish on their pinned recipe instead
+        require!(no_outputs_handled(vault_auth), TransmuterError::UserLocked);
+
+        //Pool items held for the pinned outputs are swapped for the new ones
+        let vault_outputs = parse_json_vec::<OutputInfo>(&vault_auth.outputs)?;
+        release_pool_items(
+            &vault_outputs,
+            &vault_auth.handled_outputs,
+            transmuter.key(),
+            ctx.remaining_accounts,
+        )?;
+        let transmuter_outputs = parse_json_vec::<OutputInfo>(&transmuter.outputs)?;
+        let is_shuffled = reserve_pool_items(
+            &transmuter_outputs,
+            transmuter.key(),
+            ctx.remaining_accounts,
+        )?;
+
+        //Inputs already sent only stay valid if the input recipe did not change
+        if !all_inputs_resolved(vault_auth) {
+            require!(
+                vault_auth.inputs == transmuter.inputs,
+                TransmuterError::InputsChanged
+            );
+        } else {
+            ctx.accounts.vault_auth.handled_inputs = vec![None; transmuter.inputs.len()];
+            ctx.accounts.vault_auth.input_hashes = vec![None; transmuter.inputs.len()];
+        }
+
+        if is_shuffled {
+            ctx.accounts.vault_auth.pick_seed =
+                commit_pick_seed(ctx.remaining_accounts, ctx.accounts.vault_auth.key())?;
+        }
+
+        ctx.accounts.vault_auth.handled_outputs = vec![None; transmuter.outputs.len()];
+        ctx.accounts.vault_auth.recipe_version = transmuter.recipe_version;
+        ctx.accounts.vault_auth.recipe_hash = transmuter.recipe_hash;
+        ctx.accounts.vault_auth.inputs = transmuter.inputs.clone();
+        ctx.accounts.vault_auth.outputs = transmuter.outputs.clone();
+
+        Ok(())
+    }
+
+    pub fn user_claim_output_nft<'info>(
+        ctx: Context<UserClaimOutputNft>,
+        _seed: u64,
+        _vault_seed: u64,
+        input_uris: Vec<String>,
+    ) -> Result<()> {
+        let vault_auth = &ctx.accounts.vault_auth;
+        let is_first_claim = no_outputs_handled(vault_auth);
+
+        let mut transmuter = &ctx.accounts.transmuter;
+        require!(!transmuter.locked, TransmuterError::IsLocked);
+
+        if is_first_claim {
+            let is_max_reached = transmuter.transmute_max.is_some()
+                && transmuter.transmute_count >= transmuter.transmute_max.unwrap();
+            require!(!is_max_reached, TransmuterError::MaxReached);
+        }
+
+        let transmuter_outputs = parse_json_vec::<OutputInfo>(&vault_auth.outputs)?;
+        require!(
+            transmuter_outputs.len() > 0,
+            TransmuterError::IsNotClaimable
+        );
+
+        require!(
+            transmuter_outputs.len() == vault_auth.handled_outputs.len(),
+            TransmuterError::HandledOutputsUnsynced
+        );
+
+        let output_handled = all_outputs_handled(vault_auth);
+        require!(!output_handled, TransmuterError::IsComplete);
+
+        let inputs_handled = all_inputs_handled(vault_auth);
+        require!(inputs_handled, TransmuterError::MissingInputs);
+
+        for index in 0..transmuter_outputs.len() {
+            if ctx.accounts.vault_auth.handled_outputs[index] != None {
+                continue;
+            }
+
+            //handle output
+            let output_info: &OutputInfo = &transmuter_outputs[index];
+            let mut has_minted = false;
+            require!(output_info.method != "pool", TransmuterError::InvalidMethod);
+            require!(
+                output_info.token_standard == "nft",
+                TransmuterError::UnsupportedOutput
+            );
+
+            if output_info.method == "print" {
+                user_mint_print(&ctx, output_info)?;
+                has_minted = true;
+            } else if output_info.rule.is_some() {
+                let rule = output_info.rule.as_ref().unwrap();
+                let mint_info = output_info.mint_info.as_ref().unwrap();
+                require!(
+                    verify_input_uris(vault_auth, &input_uris),
+                    TransmuterError::InvalidInputUris
+                );
+
+                if rule.name == "split" {
+                    user_mint_split(&ctx, output_info, &input_uris)?;
+                    has_minted = true;
+                } else if rule.name == "merge" {
+                    user_mint_merge(&ctx, output_info, &input_uris)?;
+                    has_minted = true;
+                } else {
+                    msg!("Rule not found");
+                }
+            } else {
+                //TODO ADD COLLECTION
+                msg!("There is no rule");
+                user_mint(&ctx, output_info)?;
+                has_minted = true;
+            }
+
+            require!(has_minted, TransmuterError::MintFailed);
+            if is_first_claim {
+                ctx.accounts.transmuter.transmute_count += 1;
+            }
+            ctx.accounts.vault_auth.handled_outputs[index] = Some(ctx.accounts.mint.key());
+            break;
+        }
+
+        ctx.accounts.vault_auth.user_locked = true;
+        let is_complete = all_outputs_handled(&ctx.accounts.vault_auth);
+        ctx.accounts.vault_auth.creator_locked = !is_complete;
+        ctx.accounts
+            .transmuter
+            .track_partial_claim(is_first_claim, is_complete);
+
+        Ok(())
+    }
+
+    pub fn user_claim_output_spl<'info>(
+        ctx: Context<UserClaimOutputSpl>,
+        _seed: u64,
+        _vault_seed: u64,
+    ) -> Result<()> {
+        let vault_auth = &ctx.accounts.vault_auth;
+        let is_first_claim = no_outputs_handled(vault_auth);
+
+        let mut transmuter = &ctx.accounts.transmuter;
+        require!(!transmuter.locked, TransmuterError::IsLocked);
+
+        if is_first_claim {
+            let is_max_reached = transmuter.transmute_max.is_some()
+                && transmuter.transmute_count >= transmuter.transmute_max.unwrap();
+            require!(!is_max_reached, TransmuterError::MaxReached);
+        }
+
+        let transmuter_outputs = parse_json_vec::<OutputInfo>(&vault_auth.outputs)?;
+        require!(
+            transmuter_outputs.len() > 0,
+            TransmuterError::IsNotClaimable
+        );
+
+        require!(
+            transmuter_outputs.len() == vault_auth.handled_outputs.len(),
+            TransmuterError::HandledOutputsUnsynced
+        );
+
+        let output_handled = all_outputs_handled(vault_auth);
+        require!(!output_handled, TransmuterError::IsComplete);
+
+        let inputs_handled = all_inputs_handled(vault_auth);
+        require!(inputs_handled, TransmuterError::MissingInputs);
+
+        for index in 0..transmuter_outputs.len() {
+            if ctx.accounts.vault_auth.handled_outputs[index] != None {
+                continue;
+            }
+
+            //handle output
+            let output_info: &OutputInfo = &transmuter_outputs[index];
+            let mut has_minted = false;
+            require!(
+                output_info.token_standard == "spl",
+                TransmuterError::InvalidMethod
+            );
+
+            require!(
+                output_info.mint == Some(ctx.accounts.mint.key().to_string()),
+                TransmuterError::InvalidOutputMint
+            );
+
+            if output_info.method == "mint" {
+                let is_cap_reached = output_info.max_supply.is_some_and(|max_supply| {
+                    ctx.accounts.mint.supply.saturating_add(output_info.amount) > max_supply
+                });
+                require!(!is_cap_reached, TransmuterError::SupplyCapReached);
+
+                ctx.accounts.mint_to_user(&output_info.amount)?;
+            } else {
+                ctx.accounts.transfer_from_auth(&output_info.amount)?;
+            }
+            has_minted = true;
+
+            require!(has_minted, TransmuterError::MintFailed);
+
+            if is_first_claim {
+                ctx.accounts.transmuter.transmute_count += 1;
+            }
+            ctx.accounts.vault_auth.handled_outputs[index] = Some(ctx.accounts.user_ata.key());
+            break;
+        }
+
+        ctx.accounts.vault_auth.user_locked = true;
+        let is_complete = all_outputs_handled(&ctx.accounts.vault_auth);
+        ctx.accounts.vault_auth.creator_locked = !is_complete;
+        ctx.accounts
+            .transmuter
+            .track_partial_claim(is_first_claim, is_complete);
+
+        Ok(())
+    }
+
+    pub fn user_claim_output_core(
+        ctx: Context<UserClaimOutputCore>,
+        _seed: u64,
+        _vault_seed: u64,
+        input_uris: Vec<String>,
+    ) -> Result<()> {
+        let vault_auth = &ctx.accounts.vault_auth;
+        let is_first_claim = no_outputs_handled(vault_auth);
+
+        let transmuter = &ctx.accounts.transmuter;
+        require!(!transmuter.locked, TransmuterError::IsLocked);
+
+        if is_first_claim {
+            let is_max_reached = transmuter.transmute_max.is_some()
+                && transmuter.transmute_count >= transmuter.transmute_max.unwrap();
+            require!(!is_max_reached, TransmuterError::MaxReached);
+        }
+
+        let transmuter_outputs = parse_json_vec::<OutputInfo>(&vault_auth.outputs)?;
+        require!(
+            !transmuter_outputs.is_empty(),
+            TransmuterError::IsNotClaimable
+        );
+
+        require!(
+            transmuter_outputs.len() == vault_auth.handled_outputs.len(),
+            TransmuterError::HandledOutputsUnsynced
+        );
+
+        let output_handled = all_outputs_handled(vault_auth);
+        require!(!output_handled, TransmuterError::IsComplete);
+
+        let inputs_handled = all_inputs_handled(vault_auth);
+        require!(inputs_handled, TransmuterError::MissingInputs);
+
+        let index = vault_auth
+            .handled_outputs
+            .iter()
+            .position(|handled_output| handled_output.is_none())
+            .unwrap();
+        let output_info: &OutputInfo = &transmuter_outputs[index];
+        require!(
+            output_info.token_standard == "core",
+            TransmuterError::InvalidMethod
+        );
+
+        let collection = ctx
+            .accounts
+            .collection
+            .as_ref()
+            .map(|collection| collection.key().to_string());
+        require!(
+            collection == output_info.collection,
+            TransmuterError::InvalidCollection
+        );
+
+        if output_info.rule.is_some() {
+            require!(
+                verify_input_uris(&ctx.accounts.vault_auth, &input_uris),
+                TransmuterError::InvalidInputUris
+            );
+        }
+        user_mint_core(&ctx, output_info, &input_uris)?;
+
+        if is_first_claim {
+            ctx.accounts.transmuter.transmute_count += 1;
+        }
+        ctx.accounts.vault_auth.handled_outputs[index] = Some(ctx.accounts.asset.key());
+
+        ctx.accounts.vault_auth.user_locked = true;
+        let is_complete = all_outputs_handled(&ctx.accounts.vault_auth);
+        ctx.accounts.vault_auth.creator_locked = !is_complete;
+        ctx.accounts
+            .transmuter
+            .track_partial_claim(is_first_claim, is_complete);
+
+        Ok(())
+    }
+
+    pub fn user_claim_output_cnft(
+        ctx: Context<UserClaimOutputCnft>,
+        _seed: u64,
+        _vault_seed: u64,
+        input_uris: Vec<String>,
+    ) -> Result<()> {
+        let vault_auth = &ctx.accounts.vault_auth;
+        let is_first_claim = no_outputs_handled(vault_auth);
+
+        let transmuter = &ctx.accounts.transmuter;
+        require!(!transmuter.locked, TransmuterError::IsLocked);
+
+        if is_first_claim {
+            let is_max_reached = transmuter.transmute_max.is_some()
+                && transmuter.transmute_count >= transmuter.transmute_max.unwrap();
+            require!(!is_max_reached, TransmuterError::MaxReached);
+        }
+
+        let transmuter_outputs = parse_json_vec::<OutputInfo>(&vault_auth.outputs)?;
+        require!(
+            !transmuter_outputs.is_empty(),
+            TransmuterError::IsNotClaimable
+        );
+
+        require!(
+            transmuter_outputs.len() == vault_auth.handled_outputs.len(),
+            TransmuterError::HandledOutputsUnsynced
+        );
+
+        let output_handled = all_outputs_handled(vault_auth);
+        require!(!output_handled, TransmuterError::IsComplete);
+
+        let inputs_handled = all_inputs_handled(vault_auth);
+        require!(inputs_handled, TransmuterError::MissingInputs);
+
+        let index = vault_auth
+            .handled_outputs
+            .iter()
+            .position(|handled_output| handled_output.is_none())
+            .unwrap();
+        let output_info: &OutputInfo = &transmuter_outputs[index];
+        require!(
+            output_info.token_standard == "cnft",
+            TransmuterError::InvalidMethod
+        );
+        require!(
+            output_info.collection == Some(ctx.accounts.collection_mint.key().to_string()),
+            TransmuterError::InvalidCollection
+        );
+        require!(
+            output_info.merkle_tree == Some(ctx.accounts.merkle_tree.key().to_string()),
+            TransmuterError::InvalidMerkleTree
+        );
+
+        //Asset id is derived from the leaf nonce, read it before minting
+        let tree_config = TreeConfig::from_bytes(&ctx.accounts.tree_config.try_borrow_data()?)
+            .map_err(|_| TransmuterError::InvalidMerkleTree)?;
+        let asset_id = get_asset_id(&ctx.accounts.merkle_tree.key(), tree_config.num_minted);
+
+        if output_info.rule.is_some() {
+            require!(
+                verify_input_uris(&ctx.accounts.vault_auth, &input_uris),
+                TransmuterError::InvalidInputUris
+            );
+        }
+        user_mint_cnft(&ctx, output_info, &input_uris)?;
+
+        if is_first_claim {
+            ctx.accounts.transmuter.transmute_count += 1;
+        }
+        ctx.accounts.vault_auth.handled_outputs[index] = Some(asset_id);
+
+        ctx.accounts.vault_auth.user_locked = true;
+        let is_complete = all_outputs_handled(&ctx.accounts.vault_auth);
+        ctx.accounts.vault_auth.creator_locked = !is_complete;
+        ctx.accounts
+            .transmuter
+            .track_partial_claim(is_first_claim, is_complete);
+
+        Ok(())
+    }
+
+    pub fn user_claim_output_pool(
+        ctx: Context<UserClaimOutputPool>,
+        _seed: u64,
+        _vault_seed: u64,
+    ) -> Result<()> {
+        let vault_auth = &ctx.accounts.vault_auth;
+        let is_first_claim = no_outputs_handled(vault_auth);
+
+        let transmuter = &ctx.accounts.transmuter;
+        require!(!transmuter.locked, TransmuterError::IsLocked);
+
+        if is_first_claim {
+            let is_max_reached = transmuter.transmute_max.is_some()
+                && transmuter.transmute_count >= transmuter.transmute_max.unwrap();
+            require!(!is_max_reached, TransmuterError::MaxReached);
+        }
+
+        let transmuter_outputs = parse_json_vec::<OutputInfo>(&vault_auth.outputs)?;
+        require!(
+            !transmuter_outputs.is_empty(),
+            TransmuterError::IsNotClaimable
+        );
+
+        require!(
+            transmuter_outputs.len() == vault_auth.handled_outputs.len(),
+            TransmuterError::HandledOutputsUnsynced
+        );
 
-        ctx.accounts.transfer_to_vault();
+        let output_handled = all_outputs_handled(vault_auth);
+        require!(!output_handled, TransmuterError::IsComplete);
 
-        Ok(())
-    }
+        let inputs_handled = all_inputs_handled(vault_auth);
+        require!(inputs_handled, TransmuterError::MissingInputs);
 
-    pub fn user_cancel_input<'info>(
-        ctx: Context<UserCancelInput>,
-        _seed: u64,
-        vault_seed: u64,
-    ) -> Result<()> {
-        let transmuter = &ctx.accounts.transmuter;
-        let vault_auth = &ctx.accounts.vault_auth;
+        let index = vault_auth
+            .handled_outputs
+            .iter()
+            .position(|handled_output| handled_output.is_none())
+            .unwrap();
+        let output_info: &OutputInfo = &transmuter_outputs[index];
+        require!(output_info.method == "pool", TransmuterError::InvalidMethod);
+        require!(
+            output_info.pool == Some(ctx.accounts.pool.key().to_string()),
+            TransmuterError::InvalidPool
+        );
 
-        require!(!transmuter.locked, TransmuterError::IsLocked);
-        require!(!vault_auth.user_locked, TransmuterError::UserLocked);
+        //Pool items are handed out in order, or picked from the vault seed when shuffled
+        let pick_index = ctx.accounts.pick_index()?;
         require!(
-            is_mint_handled(vault_auth, ctx.accounts.mint.key()),
-            TransmuterError::InvalidInputAccount
+            ctx.accounts.pool.mints[pick_index] == ctx.accounts.mint.key(),
+            TransmuterError::InvalidPool
         );
 
-        ctx.accounts.transfer_from_vault(vault_seed);
+        ctx.accounts.transfer_from_auth()?;
+        ctx.accounts.pool.mints.remove(pick_index);
+        ctx.accounts.pool.reserved = ctx.accounts.pool.reserved.saturating_sub(1);
 
-        let input_info_index = vault_auth
-            .handled_inputs
-            .iter()
-            .position(|&input: &Option<Pubkey>| input == Some(ctx.accounts.mint.key()))
-            .unwrap();
-        ctx.accounts.vault_auth.handled_inputs[input_info_index] = None;
+        if is_first_claim {
+            ctx.accounts.transmuter.transmute_count += 1;
+        }
+        ctx.accounts.vault_auth.handled_outputs[index] = Some(ctx.accounts.mint.key());
+
+        ctx.accounts.vault_auth.user_locked = true;
+        let is_complete = all_outputs_handled(&ctx.accounts.vault_auth);
+        ctx.accounts.vault_auth.creator_locked = !is_complete;
+        ctx.accounts
+            .transmuter
+            .track_partial_claim(is_first_claim, is_complete);
 
         Ok(())
     }
 
-    pub fn user_claim_output_nft<'info>(
-        ctx: Context<UserClaimOutputNft>,
+    pub fn user_claim_output_sol(
+        ctx: Context<UserClaimOutputSol>,
         _seed: u64,
         _vault_seed: u64,
     ) -> Result<()> {
         let vault_auth = &ctx.accounts.vault_auth;
         let is_first_claim = no_outputs_handled(vault_auth);
 
-        let mut transmuter = &ctx.accounts.transmuter;
+        let transmuter = &ctx.accounts.transmuter;
         require!(!transmuter.locked, TransmuterError::IsLocked);
 
         if is_first_claim {
@@ -341,13 +1675,12 @@ pub mod transmuter {
             require!(!is_max_reached, TransmuterError::MaxReached);
         }
 
-        let transmuter_outputs = parse_json_vec::<OutputInfo>(&transmuter.outputs)?;
+        let transmuter_outputs = parse_json_vec::<OutputInfo>(&vault_auth.outputs)?;
         require!(
-            transmuter_outputs.len() > 0,
+            !transmuter_outputs.is_empty(),
             TransmuterError::IsNotClaimable
         );
 
-        //TODO sync handled output in case of creator post launch change
         require!(
             transmuter_outputs.len() == vault_auth.handled_outputs.len(),
             TransmuterError::HandledOutputsUnsynced
@@ -359,58 +1692,49 @@ pub mod transmuter {
         let inputs_handled = all_inputs_handled(vault_auth);
         require!(inputs_handled, TransmuterError::MissingInputs);
 
-        for index in 0..transmuter_outputs.len() {
-            if ctx.accounts.vault_auth.handled_outputs[index] != None {
-                continue;
-            }
-
-            //handle output
-            let output_info: &OutputInfo = &transmuter_outputs[index];
-            let mut has_minted = false;
-
-            if output_info.rule.is_some() {
-                let rule = output_info.rule.as_ref().unwrap();
-                let mint_info = output_info.mint_info.as_ref().unwrap();
+        let index = vault_auth
+            .handled_outputs
+            .iter()
+            .position(|handled_output| handled_output.is_none())
+            .unwrap();
+        let output_info: &OutputInfo = &transmuter_outputs[index];
+        require!(
+            output_info.token_standard == "sol",
+            TransmuterError::InvalidMethod
+        );
+        require!(
+            output_info.escrow == Some(ctx.accounts.escrow.key().to_string()),
+            TransmuterError::InvalidEscrow
+        );
 
-                if rule.name == "split" {
-                    user_mint_split(&ctx, output_info);
-                    has_minted = true;
-                } else if rule.name == "merge" {
-                    user_mint_merge(&ctx, output_info);
-                    has_minted = true;
-                } else {
-                    msg!("Rule not found");
-                }
-            } else {
-                //TODO ADD COLLECTION
-                msg!("There is no rule");
-                user_mint(&ctx, output_info);
-                has_minted = true;
-            }
+        ctx.accounts.transfer_from_escrow(output_info.amount)?;
 
-            require!(has_minted, TransmuterError::MintFailed);
-            if is_first_claim {
-                ctx.accounts.transmuter.transmute_count += 1;
-            }
-            ctx.accounts.vault_auth.handled_outputs[index] = Some(ctx.accounts.mint.key());
-            break;
+        if is_first_claim {
+            ctx.accounts.transmuter.transmute_count += 1;
         }
+        ctx.accounts.vault_auth.handled_outputs[index] = Some(ctx.accounts.escrow.key());
 
         ctx.accounts.vault_auth.user_locked = true;
-        ctx.accounts.vault_auth.creator_locked = !all_outputs_handled(&ctx.accounts.vault_auth);
+        let is_complete = all_outputs_handled(&ctx.accounts.vault_auth);
+        ctx.accounts.vault_auth.creator_locked = !is_complete;
+        ctx.accounts
+            .transmuter
+            .track_partial_claim(is_first_claim, is_complete);
 
         Ok(())
     }
 
-    pub fn user_claim_output_spl<'info>(
-        ctx: Context<UserClaimOutputSpl>,
+    pub fn user_claim_outputs<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UserClaimOutputs<'info>>,
         _seed: u64,
         _vault_seed: u64,
+        indexes_json: String,
+        input_uris: Vec<String>,
     ) -> Result<()> {
         let vault_auth = &ctx.accounts.vault_auth;
         let is_first_claim = no_outputs_handled(vault_auth);
 
-        let mut transmuter = &ctx.accounts.transmuter;
+        let transmuter = &ctx.accounts.transmuter;
         require!(!transmuter.locked, TransmuterError::IsLocked);
 
         if is_first_claim {
@@ -419,13 +1743,12 @@ pub mod transmuter {
             require!(!is_max_reached, TransmuterError::MaxReached);
         }
 
-        let transmuter_outputs = parse_json_vec::<OutputInfo>(&transmuter.outputs)?;
+        let transmuter_outputs = parse_json_vec::<OutputInfo>(&vault_auth.outputs)?;
         require!(
-            transmuter_outputs.len() > 0,
+            !transmuter_outputs.is_empty(),
             TransmuterError::IsNotClaimable
         );
 
-        //TODO sync handled output in case of creator post launch change
         require!(
             transmuter_outputs.len() == vault_auth.handled_outputs.len(),
             TransmuterError::HandledOutputsUnsynced
@@ -437,29 +1760,179 @@ pub mod transmuter {
         let inputs_handled = all_inputs_handled(vault_auth);
         require!(inputs_handled, TransmuterError::MissingInputs);
 
-        for index in 0..transmuter_outputs.len() {
-            if ctx.accounts.vault_auth.handled_outputs[index] != None {
-                continue;
-            }
+        //One account group per unhandled output, in output order
+        let output_indexes = parse_json::<Vec<Indexes>>(&indexes_json)?;
+        let unhandled_outputs: Vec<usize> = (0..transmuter_outputs.len())
+            .filter(|&index| vault_auth.handled_outputs[index].is_none())
+            .collect();
+        require!(
+            output_indexes.len() == unhandled_outputs.len(),
+            TransmuterError::InvalidIndex
+        );
+        //Pool, print, core, cnft and sol outputs are claimed with their own instruction first
+        require!(
+            unhandled_outputs
+                .iter()
+                .all(|&index| is_batchable_output(&transmuter_outputs[index])),
+            TransmuterError::UnsupportedOutput
+        );
 
-            //handle output
-            let output_info: &OutputInfo = &transmuter_outputs[index];
-            let mut has_minted = false;
+        let has_rule = unhandled_outputs
+            .iter()
+            .any(|&index| transmuter_outputs[index].rule.is_some());
+        if has_rule {
+            require!(
+                verify_input_uris(vault_auth, &input_uris),
+                TransmuterError::InvalidInputUris
+            );
+        }
 
-            ctx.accounts.transfer_from_auth(&output_info.amount);
-            has_minted = true;
+        let output_cpi = ctx.accounts.output_cpi();
+        for (index, indexes) in unhandled_outputs.into_iter().zip(output_indexes.iter()) {
+            let handled_output = user_claim_batched_output(
+                &output_cpi,
+                ctx.remaining_accounts,
+                &input_uris,
+                &transmuter_outputs[index],
+                indexes,
+            )?;
+            ctx.accounts.vault_auth.handled_outputs[index] = Some(handled_output);
+        }
 
-            require!(has_minted, TransmuterError::MintFailed);
+        if is_first_claim {
+            ctx.accounts.transmuter.transmute_count += 1;
+        }
 
-            if is_first_claim {
-                ctx.accounts.transmuter.transmute_count += 1;
+        ctx.accounts.vault_auth.user_locked = true;
+        let is_complete = all_outputs_handled(&ctx.accounts.vault_auth);
+        ctx.accounts.vault_auth.creator_locked = !is_complete;
+        ctx.accounts
+            .transmuter
+            .track_partial_claim(is_first_claim, is_complete);
+
+        Ok(())
+    }
+
+    //Sends nft inputs and claims batched outputs in one go, core and cnft inputs or
+    //print, pool, core, cnft and sol outputs go through the vault instructions instead
+    pub fn user_transmute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UserTransmute<'info>>,
+        _seed: u64,
+        input_indexes_json: String,
+        output_indexes_json: String,
+    ) -> Result<()> {
+        let transmuter = &ctx.accounts.transmuter;
+        require!(!transmuter.locked, TransmuterError::IsLocked);
+
+        let is_max_reached = transmuter.transmute_max.is_some()
+            && transmuter.transmute_count >= transmuter.transmute_max.unwrap();
+        require!(!is_max_reached, TransmuterError::MaxReached);
+
+        let transmuter_inputs = parse_json_vec::<InputInfo>(&transmuter.inputs)?;
+        let transmuter_outputs = parse_json_vec::<OutputInfo>(&transmuter.outputs)?;
+        require!(!transmuter_inputs.is_empty(), TransmuterError::InputsNotSet);
+        require!(
+            !transmuter_outputs.is_empty(),
+            TransmuterError::OutputsNotSet
+        );
+        require!(
+            transmuter_inputs
+                .iter()
+                .all(|input_info| input_info.token_standard == "nft"),
+            TransmuterError::UnsupportedInput
+        );
+        require!(
+            transmuter_outputs.iter().all(is_batchable_output),
+            TransmuterError::UnsupportedOutput
+        );
+
+        let input_indexes = parse_json::<Vec<Indexes>>(&input_indexes_json)?;
+        let output_indexes = parse_json::<Vec<Indexes>>(&output_indexes_json)?;
+        require!(
+            input_indexes.len() == transmuter_inputs.len(),
+            TransmuterError::MissingInputs
+        );
+        require!(
+            output_indexes.len() == transmuter_outputs.len(),
+            TransmuterError::InvalidIndex
+        );
+
+        //No vault is created, inputs and their uris are only tracked for output rules
+        let mut handled_inputs: Vec<Option<Pubkey>> = vec![None; transmuter_inputs.len()];
+        let mut input_uris: Vec<String> = vec![String::new(); transmuter_inputs.len()];
+
+        let get_account = |index: Option<usize>| -> Result<&'info AccountInfo<'info>> {
+            index
+                .and_then(|index| ctx.remaining_accounts.get(index))
+                .ok_or(error!(TransmuterError::InvalidIndex))
+        };
+
+        for indexes in input_indexes.iter() {
+            let mint = get_account(Some(indexes.mint))?;
+            let ata = get_account(indexes.ata)?;
+            let metadata = get_account(indexes.metadata)?;
+
+            let user_ata = Account::<TokenAccount>::try_from(ata)?;
+            require!(
+                user_ata.mint == mint.key() && user_ata.owner == ctx.accounts.user.key(),
+                TransmuterError::InvalidUserAta
+            );
+            require!(
+                metadata.key() == Metadata::find_pda(&mint.key()).0,
+                TransmuterError::InvalidMetadata
+            );
+            require!(
+                !handled_inputs.contains(&Some(mint.key())),
+                TransmuterError::DuplicateInputAccount
+            );
+
+            let mut slot_index = None;
+            for (index, input_info) in transmuter_inputs.iter().enumerate() {
+                if handled_inputs[index].is_some() {
+                    continue;
+                }
+
+                if is_matching_nft(metadata, input_info)? {
+                    slot_index = Some(index);
+                    break;
+                }
+            }
+            let index = slot_index.ok_or(TransmuterError::InvalidInputAccount)?;
+
+            let input_metadata: Metadata = Metadata::try_from(metadata)?;
+            handled_inputs[index] = Some(mint.key());
+            input_uris[index] = input_metadata.uri;
+
+            //Inputs are resolved right away instead of waiting for the creator
+            match transmuter_inputs[index].method.as_str() {
+                "burn" => ctx.accounts.burn_input(mint, ata)?,
+                "transfer" => {
+                    let creator_ata = get_account(indexes.creator_ata)?;
+                    let creator_token_account = Account::<TokenAccount>::try_from(creator_ata)?;
+                    require!(
+                        creator_token_account.mint == mint.key()
+                            && creator_token_account.owner == ctx.accounts.creator.key(),
+                        TransmuterError::InvalidCreatorAta
+                    );
+
+                    ctx.accounts.transfer_input(ata, creator_ata)?;
+                }
+                _ => return err!(TransmuterError::InvalidResolveMethod),
             }
-            ctx.accounts.vault_auth.handled_outputs[index] = Some(ctx.accounts.user_ata.key());
-            break;
         }
 
-        ctx.accounts.vault_auth.user_locked = true;
-        ctx.accounts.vault_auth.creator_locked = !all_outputs_handled(&ctx.accounts.vault_auth);
+        let output_cpi = ctx.accounts.output_cpi();
+        for (index, indexes) in output_indexes.iter().enumerate() {
+            user_claim_batched_output(
+                &output_cpi,
+                ctx.remaining_accounts,
+                &input_uris,
+                &transmuter_outputs[index],
+                indexes,
+            )?;
+        }
+
+        ctx.accounts.transmuter.transmute_count += 1;
 
         Ok(())
     }
@@ -470,8 +1943,7 @@ pub mod transmuter {
         _seed: u64,
         vault_seed: u64,
     ) -> Result<()> {
-        let transmuter = &ctx.accounts.transmuter;
-        let transmuter_inputs = parse_json_vec::<InputInfo>(&transmuter.inputs)?;
+        let transmuter_inputs = parse_json_vec::<InputInfo>(&ctx.accounts.vault_auth.inputs)?;
         let vault_auth = &ctx.accounts.vault_auth;
 
         require!(!vault_auth.creator_locked, TransmuterError::NotClaimed);
@@ -495,7 +1967,8 @@ pub mod transmuter {
             TransmuterError::InvalidResolveMethod
         );
 
-        ctx.accounts.transfer_from_vault(vault_seed);
+        ctx.accounts.transfer_from_vault(vault_seed)?;
+        ctx.accounts.close_vault(vault_seed)?;
 
         ctx.accounts.vault_auth.handled_inputs[input_info_index] = None;
 
@@ -503,6 +1976,8 @@ pub mod transmuter {
             ctx.accounts
                 .vault_auth
                 .close(ctx.accounts.user.to_account_info())?;
+            ctx.accounts.transmuter.open_vaults =
+                ctx.accounts.transmuter.open_vaults.saturating_sub(1);
         }
 
         Ok(())
@@ -513,8 +1988,7 @@ pub mod transmuter {
         _seed: u64,
         vault_seed: u64,
     ) -> Result<()> {
-        let transmuter = &ctx.accounts.transmuter;
-        let transmuter_inputs = parse_json_vec::<InputInfo>(&transmuter.inputs)?;
+        let transmuter_inputs = parse_json_vec::<InputInfo>(&ctx.accounts.vault_auth.inputs)?;
         let vault_auth = &ctx.accounts.vault_auth;
 
         require!(!vault_auth.creator_locked, TransmuterError::NotClaimed);
@@ -559,6 +2033,162 @@ pub mod transmuter {
             CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds),
             1,
         )?;
+        ctx.accounts.close_vault(vault_seed)?;
+
+        ctx.accounts.vault_auth.handled_inputs[input_info_index] = None;
+
+        if all_inputs_resolved(&ctx.accounts.vault_auth) {
+            ctx.accounts
+                .vault_auth
+                .close(ctx.accounts.user.to_account_info())?;
+            ctx.accounts.transmuter.open_vaults =
+                ctx.accounts.transmuter.open_vaults.saturating_sub(1);
+        }
+
+        Ok(())
+    }
+
+    pub fn creator_resolve_inputs<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreatorResolveInputs<'info>>,
+        _seed: u64,
+    ) -> Result<()> {
+        //Inputs come in (vault_auth, user, mint, vault, creator_ata) groups
+        let input_groups = ctx.remaining_accounts.chunks_exact(5);
+        require!(
+            input_groups.len() > 0 && input_groups.remainder().is_empty(),
+            TransmuterError::InvalidInputAccount
+        );
+
+        for input_accounts in input_groups {
+            let [vault_auth_account, user, mint, vault, creator_ata] = input_accounts else {
+                return err!(TransmuterError::InvalidInputAccount);
+            };
+
+            let mut vault_auth = Box::new(Account::<VaultAuth>::try_from(vault_auth_account)?);
+            require!(
+                vault_auth.transmuter == ctx.accounts.transmuter.key()
+                    && vault_auth.user == user.key(),
+                TransmuterError::InvalidVault
+            );
+            require!(!vault_auth.creator_locked, TransmuterError::NotClaimed);
+
+            let input_index = vault_auth
+                .handled_inputs
+                .iter()
+                .position(|&input| input == Some(mint.key()))
+                .ok_or(TransmuterError::InvalidInputAccount)?;
+            require!(
+                vault.key() == get_associated_token_address(&vault_auth.key(), &mint.key()),
+                TransmuterError::InvalidVault
+            );
+
+            //Each vault resolves against the recipe it was created with
+            let vault_inputs = parse_json_vec::<InputInfo>(&vault_auth.inputs)?;
+            let method = vault_inputs[input_index].method.as_str();
+            if method == "transfer" {
+                let creator_token_account = Account::<TokenAccount>::try_from(creator_ata)?;
+                require!(
+                    creator_token_account.owner == ctx.accounts.creator.key()
+                        && creator_token_account.mint == mint.key(),
+                    TransmuterError::InvalidCreatorAta
+                );
+            }
+
+            ctx.accounts
+                .resolve_input(&vault_auth, user, mint, vault, creator_ata, method)?;
+            vault_auth.handled_inputs[input_index] = None;
+
+            //Later groups may hit the same vault auth, persist it right away
+            if all_inputs_resolved(&vault_auth) {
+                vault_auth.close(user.clone())?;
+                ctx.accounts.transmuter.open_vaults =
+                    ctx.accounts.transmuter.open_vaults.saturating_sub(1);
+            } else {
+                vault_auth.exit(&crate::ID)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn creator_resolve_input_core(
+        ctx: Context<CreatorResolveInputCore>,
+        _seed: u64,
+        vault_seed: u64,
+    ) -> Result<()> {
+        let transmuter_inputs = parse_json_vec::<InputInfo>(&ctx.accounts.vault_auth.inputs)?;
+        let vault_auth = &ctx.accounts.vault_auth;
+
+        require!(!vault_auth.creator_locked, TransmuterError::NotClaimed);
+
+        require!(
+            is_mint_handled(vault_auth, ctx.accounts.asset.key()),
+            TransmuterError::InvalidInputAccount
+        );
+
+        let input_info_index = vault_auth
+            .handled_inputs
+            .iter()
+            .position(|&input: &Option<Pubkey>| input == Some(ctx.accounts.asset.key()))
+            .unwrap();
+        let input_info: &InputInfo = &transmuter_inputs[input_info_index];
+
+        match input_info.method.as_str() {
+            "transfer" => ctx.accounts.transfer_from_vault(vault_seed)?,
+            "burn" => ctx.accounts.burn_from_vault(vault_seed)?,
+            _ => return err!(TransmuterError::InvalidResolveMethod),
+        }
+        ctx.accounts.tip_cranker()?;
+
+        ctx.accounts.vault_auth.handled_inputs[input_info_index] = None;
+
+        if all_inputs_resolved(&ctx.accounts.vault_auth) {
+            ctx.accounts
+                .vault_auth
+                .close(ctx.accounts.user.to_account_info())?;
+            ctx.accounts.transmuter.open_vaults =
+                ctx.accounts.transmuter.open_vaults.saturating_sub(1);
+        }
+
+        Ok(())
+    }
+
+    pub fn creator_resolve_input_cnft<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreatorResolveInputCnft<'info>>,
+        _seed: u64,
+        vault_seed: u64,
+        leaf_info: LeafInfo,
+    ) -> Result<()> {
+        let transmuter_inputs = parse_json_vec::<InputInfo>(&ctx.accounts.vault_auth.inputs)?;
+        let vault_auth = &ctx.accounts.vault_auth;
+        let asset_id = get_asset_id(&ctx.accounts.merkle_tree.key(), leaf_info.nonce);
+
+        require!(!vault_auth.creator_locked, TransmuterError::NotClaimed);
+
+        require!(
+            is_mint_handled(vault_auth, asset_id),
+            TransmuterError::InvalidInputAccount
+        );
+
+        let input_info_index = vault_auth
+            .handled_inputs
+            .iter()
+            .position(|&input: &Option<Pubkey>| input == Some(asset_id))
+            .unwrap();
+        let input_info: &InputInfo = &transmuter_inputs[input_info_index];
+
+        match input_info.method.as_str() {
+            "transfer" => {
+                ctx.accounts
+                    .transfer_from_vault(vault_seed, &leaf_info, ctx.remaining_accounts)?
+            }
+            "burn" => {
+                ctx.accounts
+                    .burn_from_vault(vault_seed, &leaf_info, ctx.remaining_accounts)?
+            }
+            _ => return err!(TransmuterError::InvalidResolveMethod),
+        }
+        ctx.accounts.tip_cranker()?;
 
         ctx.accounts.vault_auth.handled_inputs[input_info_index] = None;
 
@@ -566,8 +2196,63 @@ pub mod transmuter {
             ctx.accounts
                 .vault_auth
                 .close(ctx.accounts.user.to_account_info())?;
+            ctx.accounts.transmuter.open_vaults =
+                ctx.accounts.transmuter.open_vaults.saturating_sub(1);
         }
 
         Ok(())
     }
+
+    // Crank methods
+    pub fn vault_refund<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VaultRefund<'info>>,
+        _seed: u64,
+        vault_seed: u64,
+        leaves: Vec<VaultLeaf>,
+    ) -> Result<()> {
+        let vault_auth = &ctx.accounts.vault_auth;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            vault_auth.is_expired(&ctx.accounts.transmuter, now),
+            TransmuterError::VaultNotExpired
+        );
+
+        //Unclaimed vaults go back to the user, claimed vaults the creator never resolved are resolved
+        //by method: "burn" inputs are burned and the others go to the creator
+        //A partially claimed vault can still be finished by the user on its pinned recipe
+        let is_claimed = !no_outputs_handled(vault_auth);
+        let recipient = if is_claimed {
+            require!(!vault_auth.creator_locked, TransmuterError::IsNotClaimable);
+            ctx.accounts.creator.to_account_info()
+        } else {
+            ctx.accounts.user.to_account_info()
+        };
+
+        let transmuter_inputs = parse_json_vec::<InputInfo>(&vault_auth.inputs)?;
+        ctx.accounts
+            .input_cpi(recipient, vault_seed)
+            .return_inputs(
+                &transmuter_inputs,
+                &vault_auth.handled_inputs,
+                ctx.remaining_accounts,
+                &leaves,
+                is_claimed,
+            )?;
+
+        //Pools follow the input accounts
+        let transmuter_outputs = parse_json_vec::<OutputInfo>(&vault_auth.outputs)?;
+        release_pool_items(
+            &transmuter_outputs,
+            &vault_auth.handled_outputs,
+            ctx.accounts.transmuter.key(),
+            ctx.remaining_accounts,
+        )?;
+
+        //Vault auth is closed to the user once every input is back
+        let input_count = ctx.accounts.vault_auth.handled_inputs.len();
+        ctx.accounts.vault_auth.handled_inputs = vec![None; input_count];
+        ctx.accounts.transmuter.open_vaults = ctx.accounts.transmuter.open_vaults.saturating_sub(1);
+
+        Ok(())
+    }
 }
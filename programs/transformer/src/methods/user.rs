@@ -1,15 +1,24 @@
-use crate::{uri_from_traits, InputInfo, OutputInfo, Rule, TransmuterError, VaultAuth};
-use crate::{utils::*, UserClaimOutputNft};
+use crate::{uri_from_traits, Indexes, InputInfo, OutputInfo, Rule, TransmuterError};
+use crate::{utils::*, UserClaimOutputCnft, UserClaimOutputCore, UserClaimOutputNft};
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{Mint, TokenAccount};
+use mpl_bubblegum::types::{Collection, Creator, MetadataArgs, TokenProgramVersion, TokenStandard};
+use mpl_token_metadata::accounts::{MasterEdition, Metadata};
+use std::str::FromStr;
 use url::Url;
 
-pub fn user_mint_split(ctx: &Context<UserClaimOutputNft>, output_info: &OutputInfo) -> Result<()> {
+pub fn user_mint_split(
+    ctx: &Context<UserClaimOutputNft>,
+    output_info: &OutputInfo,
+    input_uris: &[String],
+) -> Result<()> {
     let rule = output_info.rule.as_ref().unwrap();
     let mint_info = output_info.mint_info.as_ref().unwrap();
 
     msg!("Split rule");
     // NB: index 0 because it should only be 1 input uri
-    let input_uri = ctx.accounts.vault_auth.input_uris[0].clone().unwrap();
+    let input_uri = input_uris[0].clone();
     let split_traits = get_matching_traits(input_uri, rule);
     msg!("split_traits 0: {:?}", split_traits[0]);
 
@@ -20,76 +29,291 @@ pub fn user_mint_split(ctx: &Context<UserClaimOutputNft>, output_info: &OutputIn
     // let output_collection = &output_info.collection;
 
     //mint as much as input traits (max output)
-    let result = &ctx.accounts.mint_token();
-    let _ = &ctx.accounts.create_metadata(
+    ctx.accounts.mint_token()?;
+    ctx.accounts.create_metadata(
         &mint_info.title,
         &mint_info.symbol,
         &mint_uri,
         &output_info.collection,
         500,
-    );
-    let _ = &ctx.accounts.create_master_edition();
-    let _ = &ctx.accounts.update_authority();
+    )?;
+    ctx.accounts.create_master_edition()?;
+    ctx.accounts.update_authority()?;
     //SET COLLECTION
-    
-    match result {
-        Ok(res) => res,
-        Err(_e) => panic!("{}", TransmuterError::MintFailed),
-    };
 
     Ok(())
 }
 
-pub fn user_mint_merge(ctx: &Context<UserClaimOutputNft>, output_info: &OutputInfo) -> Result<()> {
+pub fn user_mint_merge(
+    ctx: &Context<UserClaimOutputNft>,
+    output_info: &OutputInfo,
+    input_uris: &[String],
+) -> Result<()> {
     let rule = output_info.rule.as_ref().unwrap();
     let mint_info = output_info.mint_info.as_ref().unwrap();
-    let vault_auth = &ctx.accounts.vault_auth;
 
     let mut trait_values: Vec<(String, String)> = Vec::new();
-    for j in 0..vault_auth.input_uris.len() {
-        let input_uri = vault_auth.input_uris[j].clone().unwrap();
-        let mut matching_traits = get_matching_traits(input_uri, rule);
+    for input_uri in input_uris.iter() {
+        let mut matching_traits = get_matching_traits(input_uri.clone(), rule);
         trait_values.append(&mut matching_traits);
     }
 
     let uri = uri_from_traits(&mint_info.uri, trait_values);
 
-    let result = &ctx.accounts.mint_token();
-    let _ = &ctx.accounts.create_metadata(
+    ctx.accounts.mint_token()?;
+    ctx.accounts.create_metadata(
         &mint_info.title,
         &mint_info.symbol,
         &uri,
         &output_info.collection,
         500,
-    );
-    let _ = &ctx.accounts.create_master_edition();
-    let _ = &ctx.accounts.update_authority();
-
-    match result {
-        Ok(res) => res,
-        Err(_e) => panic!("{}", TransmuterError::MintFailed),
-    };
+    )?;
+    ctx.accounts.create_master_edition()?;
+    ctx.accounts.update_authority()?;
 
     Ok(())
 }
 
 pub fn user_mint(ctx: &Context<UserClaimOutputNft>, output_info: &OutputInfo) -> Result<()> {
     let mint_info = output_info.mint_info.as_ref().unwrap();
-    let result = ctx.accounts.mint_token();
-    let _ = ctx.accounts.create_metadata(
+    ctx.accounts.mint_token()?;
+    ctx.accounts.create_metadata(
         &mint_info.title,
         &mint_info.symbol,
         &mint_info.uri,
         &output_info.collection,
         500,
+    )?;
+    ctx.accounts.create_master_edition()?;
+    ctx.accounts.update_authority()?;
+
+    Ok(())
+}
+
+pub fn get_output_traits(input_uris: &[String], output_info: &OutputInfo) -> Vec<(String, String)> {
+    let mut trait_values: Vec<(String, String)> = Vec::new();
+    let Some(rule) = output_info.rule.as_ref() else {
+        return trait_values;
+    };
+
+    if rule.name == "split" {
+        // NB: index 0 because it should only be 1 input uri
+        let input_uri = input_uris[0].clone();
+        trait_values = get_matching_traits(input_uri, rule);
+    } else if rule.name == "merge" {
+        for input_uri in input_uris.iter() {
+            let mut matching_traits = get_matching_traits(input_uri.clone(), rule);
+            trait_values.append(&mut matching_traits);
+        }
+    } else {
+        msg!("Rule not found");
+    }
+
+    trait_values
+}
+
+pub fn user_mint_core(
+    ctx: &Context<UserClaimOutputCore>,
+    output_info: &OutputInfo,
+    input_uris: &[String],
+) -> Result<()> {
+    let mint_info = output_info.mint_info.as_ref().unwrap();
+    let traits = get_output_traits(input_uris, output_info);
+
+    let uri = if traits.is_empty() {
+        mint_info.uri.clone()
+    } else {
+        uri_from_traits(&mint_info.uri, traits.clone())
+    };
+
+    ctx.accounts.create_asset(&mint_info.title, &uri, &traits)
+}
+
+pub fn user_mint_cnft(
+    ctx: &Context<UserClaimOutputCnft>,
+    output_info: &OutputInfo,
+    input_uris: &[String],
+) -> Result<()> {
+    let mint_info = output_info.mint_info.as_ref().unwrap();
+    let traits = get_output_traits(input_uris, output_info);
+
+    let uri = if traits.is_empty() {
+        mint_info.uri.clone()
+    } else {
+        uri_from_traits(&mint_info.uri, traits)
+    };
+
+    let collection = output_info
+        .collection
+        .as_ref()
+        .map(|collection| Pubkey::from_str(collection).unwrap());
+
+    let metadata = MetadataArgs {
+        name: mint_info.title.to_string(),
+        symbol: mint_info.symbol.to_string(),
+        uri,
+        seller_fee_basis_points: 500,
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: Some(TokenStandard::NonFungible),
+        collection: collection.map(|key| Collection {
+            verified: false,
+            key,
+        }),
+        uses: None,
+        token_program_version: TokenProgramVersion::Original,
+        creators: vec![
+            Creator {
+                address: ctx.accounts.auth.key(),
+                verified: true,
+                share: 0,
+            },
+            Creator {
+                address: ctx.accounts.creator.key(),
+                verified: false,
+                share: 100,
+            },
+        ],
+    };
+
+    ctx.accounts.mint_to_collection(metadata)
+}
+
+pub fn user_mint_print(ctx: &Context<UserClaimOutputNft>, output_info: &OutputInfo) -> Result<()> {
+    let source_ata = ctx
+        .accounts
+        .source_ata
+        .as_ref()
+        .ok_or(TransmuterError::InvalidMasterEdition)?;
+    require!(
+        output_info.mint == Some(source_ata.mint.to_string()) && source_ata.amount == 1,
+        TransmuterError::InvalidMasterEdition
     );
-    let _ = ctx.accounts.create_master_edition();
-    let _ = ctx.accounts.update_authority();
 
-    match result {
-        Ok(res) => res,
-        Err(_e) => panic!("{}", TransmuterError::MintFailed),
+    let source_edition = ctx
+        .accounts
+        .source_edition
+        .as_ref()
+        .ok_or(TransmuterError::InvalidMasterEdition)?;
+    require!(
+        source_edition.key() == MasterEdition::find_pda(&source_ata.mint).0,
+        TransmuterError::InvalidEdition
+    );
+
+    let source_metadata = ctx
+        .accounts
+        .source_metadata
+        .as_ref()
+        .ok_or(TransmuterError::InvalidMasterEdition)?;
+    require!(
+        source_metadata.key() == Metadata::find_pda(&source_ata.mint).0,
+        TransmuterError::InvalidMetadata
+    );
+    let master_edition = MasterEdition::try_from(&source_edition.to_account_info())?;
+
+    ctx.accounts.mint_token()?;
+    ctx.accounts.create_print(master_edition.supply + 1)
+}
+
+//Batches only mint nfts or move spl tokens, other outputs have their own claim instruction
+pub fn is_batchable_output(output_info: &OutputInfo) -> bool {
+    match output_info.token_standard.as_str() {
+        "nft" => output_info.method != "print" && output_info.method != "pool",
+        "spl" => true,
+        _ => false,
+    }
+}
+
+//Claims one output of a batch, returns the key stored in handled outputs
+pub fn user_claim_batched_output<'info>(
+    cpi: &OutputCpi<'info>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    input_uris: &[String],
+    output_info: &OutputInfo,
+    indexes: &Indexes,
+) -> Result<Pubkey> {
+    let get_account = |index: Option<usize>| -> Result<&'info AccountInfo<'info>> {
+        index
+            .and_then(|index| remaining_accounts.get(index))
+            .ok_or(error!(TransmuterError::InvalidIndex))
     };
 
-    Ok(())
+    let mint = get_account(Some(indexes.mint))?;
+    let ata = get_account(indexes.ata)?;
+    let user_ata = Account::<TokenAccount>::try_from(ata)?;
+    require!(
+        user_ata.mint == mint.key() && user_ata.owner == cpi.user.key(),
+        TransmuterError::InvalidUserAta
+    );
+
+    require!(
+        is_batchable_output(output_info),
+        TransmuterError::UnsupportedOutput
+    );
+
+    match output_info.token_standard.as_str() {
+        "nft" => {
+            let metadata = get_account(indexes.metadata)?;
+            let master_edition = get_account(indexes.master_edition)?;
+            require!(
+                metadata.key() == Metadata::find_pda(&mint.key()).0,
+                TransmuterError::InvalidMetadata
+            );
+            require!(
+                master_edition.key() == MasterEdition::find_pda(&mint.key()).0,
+                TransmuterError::InvalidEdition
+            );
+
+            let mint_info = output_info.mint_info.as_ref().unwrap();
+            let traits = get_output_traits(input_uris, output_info);
+            let uri = if traits.is_empty() {
+                mint_info.uri.clone()
+            } else {
+                uri_from_traits(&mint_info.uri, traits)
+            };
+
+            cpi.mint_token(mint, ata)?;
+            cpi.create_metadata(
+                mint,
+                metadata,
+                &mint_info.title,
+                &mint_info.symbol,
+                &uri,
+                &output_info.collection,
+                500,
+            )?;
+            cpi.create_master_edition(mint, metadata, master_edition)?;
+            cpi.update_authority(mint, metadata)?;
+
+            Ok(mint.key())
+        }
+        "spl" => {
+            require!(
+                output_info.mint == Some(mint.key().to_string()),
+                TransmuterError::InvalidOutputMint
+            );
+
+            if output_info.method == "mint" {
+                let mint_account = Account::<Mint>::try_from(mint)?;
+                let is_cap_reached = output_info.max_supply.is_some_and(|max_supply| {
+                    mint_account.supply.saturating_add(output_info.amount) > max_supply
+                });
+                require!(!is_cap_reached, TransmuterError::SupplyCapReached);
+
+                cpi.mint_to_user(mint, ata, output_info.amount)?;
+            } else {
+                let auth_ata = get_account(indexes.auth_ata)?;
+                require!(
+                    auth_ata.key() == get_associated_token_address(&cpi.auth.key(), &mint.key()),
+                    TransmuterError::InvalidEscrow
+                );
+
+                cpi.transfer_from_auth(auth_ata, ata, output_info.amount)?;
+            }
+
+            Ok(ata.key())
+        }
+        _ => err!(TransmuterError::UnsupportedOutput),
+    }
 }
@@ -1,3 +1,4 @@
+use anchor_lang::prelude::*;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -6,6 +7,26 @@ pub struct Config {
     pub output_length: u64,
     pub transmute_max: Option<u64>,
     pub traits_uri: Option<String>,
+    pub vault_ttl_seconds: Option<i64>,
+    #[serde(default)]
+    pub creator_resolve_only: bool,
+    pub recipe_timelock_seconds: Option<i64>,
+}
+
+//Settings transmuter_set can change, staged like the recipe when timelocked
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigUpdate {
+    pub transmute_max: Option<u64>,
+    pub traits_uri: Option<String>,
+    pub vault_ttl_seconds: Option<i64>,
+    pub creator_resolve_only: bool,
+}
+
+impl ConfigUpdate {
+    pub const LEN: usize = 9 //Option<u64>
+    + 1 + 24 //Option<String>
+    + 9 //Option<i64>
+    + 1; //bool
 }
 
 impl Config {
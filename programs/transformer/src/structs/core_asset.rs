@@ -0,0 +1,129 @@
+use crate::{TransmuterError, MPL_CORE_ID};
+use anchor_lang::prelude::*;
+
+// Metaplex Core account keys and plugin types (see mpl-core `Key` and `PluginType`)
+pub const CORE_ASSET_KEY: u8 = 1;
+pub const CORE_PLUGIN_HEADER_KEY: u8 = 3;
+pub const CORE_ATTRIBUTES_PLUGIN: u8 = 6;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum CoreUpdateAuthority {
+    None,
+    Address(Pubkey),
+    Collection(Pubkey),
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum CoreAuthority {
+    None,
+    Owner,
+    UpdateAuthority,
+    Address { address: Pubkey },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CoreAttribute {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct CorePluginHeader {
+    pub key: u8,
+    pub plugin_registry_offset: u64,
+}
+
+// Mirrors the on-chain layout, not every field is read
+#[allow(dead_code)]
+#[derive(AnchorDeserialize)]
+pub struct CoreRegistryRecord {
+    pub plugin_type: u8,
+    pub authority: CoreAuthority,
+    pub offset: u64,
+}
+
+#[allow(dead_code)]
+#[derive(AnchorDeserialize)]
+pub struct CorePluginRegistry {
+    pub key: u8,
+    pub registry: Vec<CoreRegistryRecord>,
+}
+
+#[allow(dead_code)]
+#[derive(AnchorDeserialize)]
+pub struct CoreAsset {
+    pub key: u8,
+    pub owner: Pubkey,
+    pub update_authority: CoreUpdateAuthority,
+    pub name: String,
+    pub uri: String,
+    pub seq: Option<u64>,
+    #[borsh_skip]
+    pub attributes: Vec<(String, String)>,
+}
+
+impl CoreAsset {
+    pub fn try_from(account: &AccountInfo) -> Result<CoreAsset> {
+        require!(
+            *account.owner == MPL_CORE_ID,
+            TransmuterError::InvalidCoreAsset
+        );
+
+        let data = account.try_borrow_data()?;
+        let mut base_data: &[u8] = &data;
+        let mut asset = CoreAsset::deserialize(&mut base_data)
+            .map_err(|_| TransmuterError::InvalidCoreAsset)?;
+        require!(
+            asset.key == CORE_ASSET_KEY,
+            TransmuterError::InvalidCoreAsset
+        );
+
+        //Plugins are appended after the base asset
+        if !base_data.is_empty() {
+            let header = CorePluginHeader::deserialize(&mut base_data)
+                .map_err(|_| TransmuterError::InvalidCoreAsset)?;
+            require!(
+                header.key == CORE_PLUGIN_HEADER_KEY,
+                TransmuterError::InvalidCoreAsset
+            );
+
+            let mut registry_data = data
+                .get(header.plugin_registry_offset as usize..)
+                .ok_or(TransmuterError::InvalidCoreAsset)?;
+            let registry = CorePluginRegistry::deserialize(&mut registry_data)
+                .map_err(|_| TransmuterError::InvalidCoreAsset)?;
+
+            for record in registry.registry.iter() {
+                if record.plugin_type != CORE_ATTRIBUTES_PLUGIN {
+                    continue;
+                }
+
+                let mut plugin_data = data
+                    .get(record.offset as usize..)
+                    .ok_or(TransmuterError::InvalidCoreAsset)?;
+                let plugin_type = u8::deserialize(&mut plugin_data)
+                    .map_err(|_| TransmuterError::InvalidCoreAsset)?;
+                require!(
+                    plugin_type == CORE_ATTRIBUTES_PLUGIN,
+                    TransmuterError::InvalidCoreAsset
+                );
+
+                let attribute_list = Vec::<CoreAttribute>::deserialize(&mut plugin_data)
+                    .map_err(|_| TransmuterError::InvalidCoreAsset)?;
+                asset.attributes = attribute_list
+                    .into_iter()
+                    .map(|attribute| (attribute.key, attribute.value))
+                    .collect();
+            }
+        }
+
+        Ok(asset)
+    }
+
+    pub fn collection(&self) -> Option<Pubkey> {
+        match self.update_authority {
+            CoreUpdateAuthority::Collection(collection) => Some(collection),
+            _ => None,
+        }
+    }
+}
@@ -3,8 +3,9 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize)]
 pub struct Indexes {
     pub mint: usize,
-    pub metadata: usize,
+    pub metadata: Option<usize>,
     pub ata: Option<usize>,
     pub creator_ata: Option<usize>,
     pub master_edition: Option<usize>,
+    pub auth_ata: Option<usize>,
 }
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LeafInfo {
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+}
+
+//Leaf of a held cnft returned from remaining accounts, followed by proof_len proof nodes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VaultLeaf {
+    pub leaf_info: LeafInfo,
+    pub proof_len: u8,
+}
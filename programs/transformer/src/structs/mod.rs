@@ -1,19 +1,29 @@
 pub mod config;
+pub mod core_asset;
 pub mod indexes;
 pub mod input;
+pub mod leaf;
 pub mod mint;
+pub mod operator;
 pub mod output;
+pub mod pool;
 pub mod rule;
+pub mod sol_escrow;
 pub mod traits;
 pub mod transmuter;
 pub mod vault_auth;
 
 pub use config::*;
+pub use core_asset::*;
 pub use indexes::*;
 pub use input::*;
+pub use leaf::*;
 pub use mint::*;
+pub use operator::*;
 pub use output::*;
+pub use pool::*;
 pub use rule::*;
+pub use sol_escrow::*;
 pub use traits::*;
 pub use transmuter::*;
 pub use vault_auth::*;
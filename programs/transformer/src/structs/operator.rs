@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+//Role bitflags, the authority implicitly holds all of them
+pub const ROLE_PAUSE: u8 = 1 << 0;
+pub const ROLE_EDIT: u8 = 1 << 1;
+pub const ROLE_RESOLVE: u8 = 1 << 2;
+pub const ROLE_WITHDRAW: u8 = 1 << 3;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Operator {
+    pub key: Pubkey,
+    pub roles: u8,
+}
+
+impl Operator {
+    pub const LEN: usize = 32 //Pubkey
+    + 1; //u8
+    pub const MAX: usize = 5;
+}
@@ -13,6 +13,10 @@ pub struct OutputInfo {
     pub uri: Option<String>,
     pub mint_info: Option<MintInfo>,
     pub mint: Option<String>,
+    pub merkle_tree: Option<String>,
+    pub pool: Option<String>,
+    pub max_supply: Option<u64>,
+    pub escrow: Option<String>,
 }
 
 impl OutputInfo {
@@ -24,5 +28,9 @@ impl OutputInfo {
     + Rule::LEN //Rule
     + 64 //String
     + MintInfo::LEN //MintInfo
+    + 44 //String (Pubkey)
+    + 44 //String (Pubkey)
+    + 44 //String (Pubkey)
+    + 9 //Option<u64>
     + 44; //String (Pubkey)
 }
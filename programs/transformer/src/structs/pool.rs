@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Pool {
+    pub transmuter: Pubkey,
+    pub seed: u64,
+    pub shuffle: bool,
+    pub pool_bump: u8,
+    pub reserved: u32,
+    pub mints: Vec<Pubkey>,
+}
+
+impl Pool {
+    pub const LEN: usize = 8 //Discriminator
+    + 32 //Pubkey
+    + 8 //u64
+    + 1 //bool
+    + 1 //u8
+    + 4 //u32
+    + 4; //Vec<Pubkey> (grown on deposit)
+
+    //Items already promised to open vaults can't go to anyone else
+    pub fn available(&self) -> usize {
+        self.mints.len().saturating_sub(self.reserved as usize)
+    }
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct SolEscrow {
+    pub transmuter: Pubkey,
+    pub seed: u64,
+    pub escrow_bump: u8,
+}
+
+impl SolEscrow {
+    pub const LEN: usize = 8 //Discriminator
+    + 32 //Pubkey
+    + 8 //u64
+    + 1; //u8
+
+    //Lamports held on top of the rent exemption
+    pub fn balance(account: &AccountInfo) -> Result<u64> {
+        let rent = Rent::get()?.minimum_balance(account.data_len());
+        Ok(account.lamports().saturating_sub(rent))
+    }
+}
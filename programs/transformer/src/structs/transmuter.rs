@@ -1,4 +1,8 @@
+use crate::{
+    parse_json_vec, Config, ConfigUpdate, InputInfo, Operator, OutputInfo, TransmuterError,
+};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
 
 #[account]
 pub struct Transmuter {
@@ -12,6 +16,23 @@ pub struct Transmuter {
     pub traits_uri: Option<String>,
     pub transmute_max: Option<u64>,
     pub transmute_count: u64,
+    pub vault_ttl_seconds: Option<i64>,
+    pub creator_resolve_only: bool,
+    pub recipe_version: u64,
+    pub recipe_hash: [u8; 32],
+    pub recipe_timelock_seconds: Option<i64>,
+    pub pending_inputs: Vec<String>,
+    pub pending_outputs: Vec<String>,
+    pub pending_effective_at: Option<i64>,
+    pub immutable: bool,
+    pub config_frozen: bool,
+    pub open_vaults: u64,
+    pub pool_count: u64,
+    pub partial_vaults: u64,
+    pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+    pub operators: Vec<Operator>,
+    pub pending_config: Option<ConfigUpdate>,
 }
 
 //Vector max size?
@@ -26,5 +47,225 @@ impl Transmuter {
     + 128 //Vec<String>
     + 24 //String
     + 8 //u64
-    + 8; //u64
+    + 8 //u64
+    + 9 //Option<i64>
+    + 1 //bool
+    + 8 //u64
+    + 32 //Hash
+    + 9 //Option<i64>
+    + 4 //Vec<String>
+    + 4 //Vec<String>
+    + 9 //Option<i64>
+    + 1 //bool
+    + 1 //bool
+    + 8 //u64
+    + 8 //u64
+    + 8 //u64
+    + 32 //Pubkey
+    + 33 //Option<Pubkey>
+    + 4 + Operator::MAX * Operator::LEN //Vec<Operator>
+    + 1 + ConfigUpdate::LEN; //Option<ConfigUpdate>
+
+    pub fn space(config: &Config) -> usize {
+        let recipe = config.input_length as usize * InputInfo::LEN
+            + config.output_length as usize * OutputInfo::LEN;
+        //Timelocked transmuters keep room for a pending copy of the recipe
+        let copies = if config.recipe_timelock_seconds.is_some() {
+            2
+        } else {
+            1
+        };
+        Transmuter::LEN + recipe * copies
+    }
+
+    //Called after every input or output change so vaults can tell they are out of date
+    //Only a recipe that actually changed moves the version, config only edits keep vaults in sync
+    pub fn bump_recipe(&mut self) -> Result<()> {
+        let mut recipe = Vec::new();
+        self.inputs.serialize(&mut recipe)?;
+        self.outputs.serialize(&mut recipe)?;
+
+        let recipe_hash = hash(&recipe).to_bytes();
+        if recipe_hash != self.recipe_hash {
+            self.recipe_version += 1;
+            self.recipe_hash = recipe_hash;
+        }
+        Ok(())
+    }
+
+    pub fn has_role(&self, key: &Pubkey, role: u8) -> bool {
+        *key == self.authority
+            || self
+                .operators
+                .iter()
+                .any(|operator| operator.key == *key && operator.roles & role == role)
+    }
+
+    pub fn is_operator(&self, key: &Pubkey) -> bool {
+        *key == self.authority || self.operators.iter().any(|operator| operator.key == *key)
+    }
+
+    pub fn check_role(&self, key: &Pubkey, role: u8) -> Result<()> {
+        require!(self.has_role(key, role), TransmuterError::MissingRole);
+        Ok(())
+    }
+
+    pub fn check_authority(&self, key: &Pubkey) -> Result<()> {
+        require!(*key == self.authority, TransmuterError::NotAuthority);
+        Ok(())
+    }
+
+    //No roles removes the operator
+    pub fn set_operator(&mut self, key: Pubkey, roles: u8) -> Result<()> {
+        self.operators.retain(|operator| operator.key != key);
+        if roles != 0 {
+            require!(
+                self.operators.len() < Operator::MAX,
+                TransmuterError::TooManyOperators
+            );
+            self.operators.push(Operator { key, roles });
+        }
+        Ok(())
+    }
+
+    pub fn push_input(&mut self, input_json: String) -> Result<()> {
+        require!(!self.immutable, TransmuterError::TransmuterImmutable);
+        match self.recipe_timelock_seconds {
+            Some(timelock) => {
+                self.stage_recipe(timelock)?;
+                self.pending_inputs.push(input_json);
+                Ok(())
+            }
+            None => {
+                self.inputs.push(input_json);
+                self.bump_recipe()
+            }
+        }
+    }
+
+    pub fn push_output(&mut self, output_json: String) -> Result<()> {
+        require!(!self.immutable, TransmuterError::TransmuterImmutable);
+        match self.recipe_timelock_seconds {
+            Some(timelock) => {
+                self.stage_recipe(timelock)?;
+                self.pending_outputs.push(output_json);
+                Ok(())
+            }
+            None => {
+                self.outputs.push(output_json);
+                self.bump_recipe()
+            }
+        }
+    }
+
+    pub fn remove_output(&mut self, index: usize) -> Result<String> {
+        require!(!self.immutable, TransmuterError::TransmuterImmutable);
+        match self.recipe_timelock_seconds {
+            Some(timelock) => {
+                self.stage_recipe(timelock)?;
+                require!(
+                    index < self.pending_outputs.len(),
+                    TransmuterError::InvalidIndex
+                );
+                Ok(self.pending_outputs.remove(index))
+            }
+            None => {
+                require!(index < self.outputs.len(), TransmuterError::InvalidIndex);
+                let output_json = self.outputs.remove(index);
+                self.bump_recipe()?;
+                Ok(output_json)
+            }
+        }
+    }
+
+    pub fn set_config(&mut self, config: ConfigUpdate) -> Result<()> {
+        match self.recipe_timelock_seconds {
+            Some(timelock) => {
+                self.stage_recipe(timelock)?;
+                self.pending_config = Some(config);
+            }
+            None => self.apply_config(config),
+        }
+        Ok(())
+    }
+
+    fn apply_config(&mut self, config: ConfigUpdate) {
+        self.transmute_max = config.transmute_max;
+        self.traits_uri = config.traits_uri;
+        self.vault_ttl_seconds = config.vault_ttl_seconds;
+        self.creator_resolve_only = config.creator_resolve_only;
+    }
+
+    //Outputs still live or pending keep their escrows
+    pub fn escrowed_outputs(&self) -> Result<Vec<OutputInfo>> {
+        let mut outputs = parse_json_vec::<OutputInfo>(&self.outputs)?;
+        outputs.extend(parse_json_vec::<OutputInfo>(&self.pending_outputs)?);
+        Ok(outputs)
+    }
+
+    //An escrow in use can only be pulled while the recipe is freely editable
+    pub fn check_escrow_withdrawable(&self, is_escrowed: bool) -> Result<()> {
+        if is_escrowed {
+            require!(!self.immutable, TransmuterError::TransmuterImmutable);
+            require!(
+                self.recipe_timelock_seconds.is_none(),
+                TransmuterError::RecipeTimelocked
+            );
+        }
+        Ok(())
+    }
+
+    //Partially claimed vaults still claim from the escrows their pinned outputs point at
+    pub fn check_escrows_unpinned(&self) -> Result<()> {
+        require!(self.partial_vaults == 0, TransmuterError::VaultsOpen);
+        Ok(())
+    }
+
+    pub fn track_partial_claim(&mut self, was_unclaimed: bool, is_complete: bool) {
+        match (was_unclaimed, is_complete) {
+            (true, false) => self.partial_vaults += 1,
+            (false, true) => self.partial_vaults = self.partial_vaults.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    //Timelocked edits land on a pending copy and push its effective time back
+    fn stage_recipe(&mut self, timelock: i64) -> Result<()> {
+        if self.pending_effective_at.is_none() {
+            self.pending_inputs = self.inputs.clone();
+            self.pending_outputs = self.outputs.clone();
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        self.pending_effective_at = Some(now.saturating_add(timelock));
+        Ok(())
+    }
+
+    pub fn apply_recipe(&mut self, now: i64) -> Result<()> {
+        let effective_at = self
+            .pending_effective_at
+            .ok_or(TransmuterError::NoPendingRecipe)?;
+        require!(now >= effective_at, TransmuterError::RecipeTimelocked);
+
+        self.inputs = std::mem::take(&mut self.pending_inputs);
+        self.outputs = std::mem::take(&mut self.pending_outputs);
+        if let Some(config) = self.pending_config.take() {
+            self.apply_config(config);
+        }
+        self.pending_effective_at = None;
+        self.bump_recipe()
+    }
+
+    pub fn cancel_recipe(&mut self) -> Result<()> {
+        require!(
+            self.pending_effective_at.is_some(),
+            TransmuterError::NoPendingRecipe
+        );
+
+        self.pending_inputs = Vec::new();
+        self.pending_outputs = Vec::new();
+        self.pending_config = None;
+        self.pending_effective_at = None;
+        Ok(())
+    }
 }
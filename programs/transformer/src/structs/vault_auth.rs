@@ -1,5 +1,9 @@
+use crate::Transmuter;
 use anchor_lang::prelude::*;
 
+//Paid to whoever resolves an input, out of the closed vault rent or the prepaid tip
+pub const RESOLVE_TIP_LAMPORTS: u64 = 5000;
+
 #[account]
 pub struct VaultAuth {
     pub transmuter: Pubkey,
@@ -10,18 +14,46 @@ pub struct VaultAuth {
     pub handled_inputs: Vec<Option<Pubkey>>,
     pub handled_outputs: Vec<Option<Pubkey>>,
     pub vault_auth_bump: u8,
-    pub input_uris: Vec<Option<String>>,
+    pub input_hashes: Vec<Option<[u8; 32]>>,
+    pub created_at: i64,
+    pub pick_seed: u64,
+    pub recipe_version: u64,
+    pub recipe_hash: [u8; 32],
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
 }
 
 impl VaultAuth {
-    pub const LEN: usize = 8 //Discriminator
-    + 32 //Pubkey
-    + 32 //Pubkey
-    + 8 //u64
-    + 1 //bool
-    + 1 //bool
-    + 5 * 32 //Vec pubkey (5 max)
-    + 5 * 32 //Vec pubkey (5 max)
-    + 1 //u8
-    + 1 * 24; //Vec string
+    //Sized from the recipe, which is pinned in full alongside one handled entry per slot
+    pub fn space(inputs: &[String], outputs: &[String]) -> usize {
+        let input_count = inputs.len();
+        let output_count = outputs.len();
+        let recipe_len = |recipe: &[String]| -> usize {
+            recipe.iter().map(|entry| 4 + entry.len()).sum::<usize>()
+        };
+
+        8 //Discriminator
+        + 32 //Pubkey
+        + 32 //Pubkey
+        + 8 //u64
+        + 1 //bool
+        + 1 //bool
+        + 4 + input_count * 33 //Vec option pubkey
+        + 4 + output_count * 33 //Vec option pubkey
+        + 1 //u8
+        + 4 + input_count * 33 //Vec option hash
+        + 8 //i64
+        + 8 //u64
+        + 8 //u64
+        + 32 //Hash
+        + 4 + recipe_len(inputs) //Vec<String>
+        + 4 + recipe_len(outputs) //Vec<String>
+    }
+
+    //Vaults never expire without a ttl on the transmuter
+    pub fn is_expired(&self, transmuter: &Transmuter, now: i64) -> bool {
+        transmuter
+            .vault_ttl_seconds
+            .is_some_and(|ttl| now >= self.created_at.saturating_add(ttl))
+    }
 }
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::pubkey;
+
+use crate::{CoreAttribute, CORE_ATTRIBUTES_PLUGIN};
+
+pub const MPL_CORE_ID: Pubkey = pubkey!("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d");
+
+// Metaplex Core instruction discriminators (see mpl-core `MplAssetInstruction`)
+const BURN_V1: u8 = 12;
+const TRANSFER_V1: u8 = 14;
+const CREATE_V2: u8 = 20;
+
+//Optional Core accounts are passed as the Core program id when missing
+fn optional_meta(account: Option<Pubkey>, is_writable: bool) -> AccountMeta {
+    match account {
+        Some(key) if is_writable => AccountMeta::new(key, false),
+        Some(key) => AccountMeta::new_readonly(key, false),
+        None => AccountMeta::new_readonly(MPL_CORE_ID, false),
+    }
+}
+
+pub fn core_transfer_ix(
+    asset: Pubkey,
+    collection: Option<Pubkey>,
+    payer: Pubkey,
+    authority: Pubkey,
+    new_owner: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: MPL_CORE_ID,
+        accounts: vec![
+            AccountMeta::new(asset, false),
+            optional_meta(collection, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(new_owner, false),
+            optional_meta(None, false),
+            optional_meta(None, false),
+        ],
+        // compression_proof: None
+        data: vec![TRANSFER_V1, 0],
+    }
+}
+
+pub fn core_burn_ix(
+    asset: Pubkey,
+    collection: Option<Pubkey>,
+    payer: Pubkey,
+    authority: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: MPL_CORE_ID,
+        accounts: vec![
+            AccountMeta::new(asset, false),
+            optional_meta(collection, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(authority, true),
+            optional_meta(None, false),
+            optional_meta(None, false),
+        ],
+        // compression_proof: None
+        data: vec![BURN_V1, 0],
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn core_create_ix(
+    asset: Pubkey,
+    collection: Option<Pubkey>,
+    authority: Pubkey,
+    payer: Pubkey,
+    owner: Pubkey,
+    update_authority: Option<Pubkey>,
+    name: &str,
+    uri: &str,
+    traits: &[(String, String)],
+) -> Result<Instruction> {
+    let attribute_list: Vec<CoreAttribute> = traits
+        .iter()
+        .map(|(key, value)| CoreAttribute {
+            key: key.to_string(),
+            value: value.to_string(),
+        })
+        .collect();
+
+    // data_state: AccountState
+    let mut data = vec![CREATE_V2, 0];
+    name.serialize(&mut data)?;
+    uri.serialize(&mut data)?;
+    // plugins: Some([Attributes with default authority])
+    data.push(1);
+    1u32.serialize(&mut data)?;
+    data.push(CORE_ATTRIBUTES_PLUGIN);
+    attribute_list.serialize(&mut data)?;
+    data.push(0);
+    // external_plugin_adapters: None
+    data.push(0);
+
+    Ok(Instruction {
+        program_id: MPL_CORE_ID,
+        accounts: vec![
+            AccountMeta::new(asset, true),
+            optional_meta(collection, true),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(owner, false),
+            optional_meta(update_authority, false),
+            AccountMeta::new_readonly(anchor_lang::system_program::ID, false),
+            optional_meta(None, false),
+        ],
+        data,
+    })
+}
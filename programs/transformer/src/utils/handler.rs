@@ -1,5 +1,7 @@
-use crate::VaultAuth;
+use crate::{OutputInfo, Pool, TransmuterError, VaultAuth, RESOLVE_TIP_LAMPORTS};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::{hash, hashv};
+use anchor_lang::solana_program::sysvar::slot_hashes;
 use solana_program::pubkey::Pubkey;
 
 pub fn all_outputs_handled(vault_auth: &Box<Account<VaultAuth>>) -> bool {
@@ -38,4 +40,128 @@ pub fn is_mint_handled(vault_auth: &Box<Account<VaultAuth>>, mint_key: Pubkey) -
         .handled_inputs
         .iter()
         .any(|&input: &Option<Pubkey>| input == Some(mint_key))
-}
\ No newline at end of file
+}
+
+pub fn hash_uri(uri: &str) -> [u8; 32] {
+    hash(uri.as_bytes()).to_bytes()
+}
+
+//Only hashes are stored, rules get the input uris back from the client
+pub fn verify_input_uris(vault_auth: &VaultAuth, input_uris: &[String]) -> bool {
+    input_uris.len() == vault_auth.input_hashes.len()
+        && input_uris
+            .iter()
+            .zip(vault_auth.input_hashes.iter())
+            .all(|(uri, input_hash)| *input_hash == Some(hash_uri(uri)))
+}
+
+//Every pool output holds one item for the vault until it is claimed, pools come from remaining accounts
+//Returns whether one of the pools is shuffled
+pub fn reserve_pool_items<'info>(
+    outputs: &[OutputInfo],
+    transmuter: Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<bool> {
+    let mut is_shuffled = false;
+    for output_info in outputs.iter() {
+        if output_info.method != "pool" {
+            continue;
+        }
+
+        let mut pool = find_pool(output_info, transmuter, accounts)?;
+        require!(pool.available() > 0, TransmuterError::PoolDepleted);
+        pool.reserved += 1;
+        is_shuffled |= pool.shuffle;
+        pool.exit(&crate::ID)?;
+    }
+    Ok(is_shuffled)
+}
+
+//Unclaimed pool outputs give their item back when the vault leaves without claiming
+pub fn release_pool_items<'info>(
+    outputs: &[OutputInfo],
+    handled_outputs: &[Option<Pubkey>],
+    transmuter: Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    for (output_info, handled_output) in outputs.iter().zip(handled_outputs.iter()) {
+        if output_info.method != "pool" || handled_output.is_some() {
+            continue;
+        }
+
+        let mut pool = find_pool(output_info, transmuter, accounts)?;
+        pool.reserved = pool.reserved.saturating_sub(1);
+        pool.exit(&crate::ID)?;
+    }
+    Ok(())
+}
+
+fn find_pool<'info>(
+    output_info: &OutputInfo,
+    transmuter: Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<Account<'info, Pool>> {
+    let pool_account = accounts
+        .iter()
+        .find(|account| Some(account.key().to_string()) == output_info.pool)
+        .ok_or(TransmuterError::InvalidPool)?;
+    let pool = Account::<Pool>::try_from(pool_account)?;
+    require!(pool.transmuter == transmuter, TransmuterError::InvalidPool);
+    Ok(pool)
+}
+
+//Pools live until the transmuter closes, so all of them can be asked for to tell pool items apart
+pub fn is_pool_mint<'info>(
+    mint: Pubkey,
+    transmuter: Pubkey,
+    pool_count: u64,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<bool> {
+    let mut pool_keys: Vec<Pubkey> = Vec::new();
+    let mut is_pooled = false;
+    for account in accounts.iter() {
+        let pool = Account::<Pool>::try_from(account)?;
+        require!(
+            pool.transmuter == transmuter && !pool_keys.contains(&account.key()),
+            TransmuterError::InvalidPool
+        );
+        pool_keys.push(account.key());
+        is_pooled |= pool.mints.contains(&mint);
+    }
+    require!(
+        pool_keys.len() as u64 == pool_count,
+        TransmuterError::InvalidPool
+    );
+    Ok(is_pooled)
+}
+
+//Shuffled pools pick from a seed committed when the vault opens, so claims know their item
+//Tips out of the vault auth lamports above its rent, never below it
+pub fn pay_resolve_tip<'info>(
+    vault_auth: &AccountInfo<'info>,
+    cranker: &AccountInfo<'info>,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(vault_auth.data_len());
+    let tip = vault_auth
+        .lamports()
+        .saturating_sub(rent)
+        .min(RESOLVE_TIP_LAMPORTS);
+
+    **vault_auth.try_borrow_mut_lamports()? -= tip;
+    **cranker.try_borrow_mut_lamports()? += tip;
+
+    Ok(())
+}
+
+pub fn commit_pick_seed(accounts: &[AccountInfo], vault_auth: Pubkey) -> Result<u64> {
+    let slot_hashes = accounts
+        .iter()
+        .find(|account| account.key() == slot_hashes::ID)
+        .ok_or(TransmuterError::InvalidPool)?;
+
+    //Skip the entry count (u64) and the latest slot (u64)
+    let data = slot_hashes.try_borrow_data()?;
+    let slot_hash = data.get(16..48).ok_or(TransmuterError::InvalidPool)?;
+    let pick_hash = hashv(&[slot_hash, vault_auth.as_ref()]).to_bytes();
+    Ok(u64::from_le_bytes(pick_hash[..8].try_into().unwrap()))
+}
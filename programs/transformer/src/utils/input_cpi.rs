@@ -0,0 +1,385 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{
+    burn, close_account, transfer, Burn, CloseAccount, TokenAccount, Transfer,
+};
+use mpl_bubblegum::instructions::{BurnCpiBuilder, TransferCpiBuilder};
+use mpl_bubblegum::utils::get_asset_id;
+
+use crate::{core_burn_ix, core_transfer_ix, InputInfo, LeafInfo, TransmuterError, VaultLeaf};
+
+//Accounts shared by every input handed back out of a vault from remaining accounts
+pub struct InputCpi<'info> {
+    pub transmuter: Pubkey,
+    pub vault_seed: u64,
+    pub vault_auth_bump: u8,
+    pub vault_auth: AccountInfo<'info>,
+    pub user: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub recipient: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub core_program: Option<AccountInfo<'info>>,
+    pub bubblegum_program: Option<AccountInfo<'info>>,
+    pub log_wrapper: Option<AccountInfo<'info>>,
+    pub compression_program: Option<AccountInfo<'info>>,
+}
+
+impl<'info> InputCpi<'info> {
+    //Every held input is returned in slot order, read from remaining accounts as
+    //(mint, vault, ata) for nft, (asset, collection) for core and (tree_config, merkle_tree, proof..) for cnft
+    //With burn_inputs, "burn" inputs are destroyed instead and nft ones skip the ata
+    pub fn return_inputs(
+        &self,
+        inputs: &[InputInfo],
+        handled_inputs: &[Option<Pubkey>],
+        remaining_accounts: &'info [AccountInfo<'info>],
+        leaves: &[VaultLeaf],
+        burn_inputs: bool,
+    ) -> Result<()> {
+        let mut accounts = remaining_accounts.iter();
+        let mut leaves = leaves.iter();
+
+        for (input_info, handled_input) in inputs.iter().zip(handled_inputs.iter()) {
+            let Some(input_key) = handled_input else {
+                continue;
+            };
+            let is_burned = burn_inputs && input_info.method == "burn";
+
+            match input_info.token_standard.as_str() {
+                "nft" if is_burned => {
+                    let (Some(mint), Some(vault)) = (accounts.next(), accounts.next()) else {
+                        return err!(TransmuterError::InvalidInputAccount);
+                    };
+                    require!(
+                        mint.key() == *input_key,
+                        TransmuterError::InvalidInputAccount
+                    );
+                    require!(
+                        vault.key()
+                            == get_associated_token_address(&self.vault_auth.key(), input_key),
+                        TransmuterError::InvalidVault
+                    );
+
+                    self.burn_token(mint, vault)?;
+                }
+                "nft" => {
+                    let (Some(mint), Some(vault), Some(ata)) =
+                        (accounts.next(), accounts.next(), accounts.next())
+                    else {
+                        return err!(TransmuterError::InvalidInputAccount);
+                    };
+                    require!(
+                        mint.key() == *input_key,
+                        TransmuterError::InvalidInputAccount
+                    );
+                    require!(
+                        vault.key()
+                            == get_associated_token_address(&self.vault_auth.key(), input_key),
+                        TransmuterError::InvalidVault
+                    );
+                    let recipient_ata = Account::<TokenAccount>::try_from(ata)?;
+                    require!(
+                        recipient_ata.owner == self.recipient.key()
+                            && recipient_ata.mint == *input_key,
+                        TransmuterError::InvalidUserAta
+                    );
+
+                    self.return_token(vault, ata)?;
+                }
+                "core" => {
+                    let (Some(asset), Some(collection)) = (accounts.next(), accounts.next()) else {
+                        return err!(TransmuterError::InvalidInputAccount);
+                    };
+                    require!(
+                        asset.key() == *input_key,
+                        TransmuterError::InvalidInputAccount
+                    );
+
+                    if is_burned {
+                        self.burn_core(asset, collection)?;
+                    } else {
+                        self.return_core(asset, collection)?;
+                    }
+                }
+                "cnft" => {
+                    let leaf = leaves.next().ok_or(TransmuterError::InvalidInputAccount)?;
+                    let (Some(tree_config), Some(merkle_tree)) = (accounts.next(), accounts.next())
+                    else {
+                        return err!(TransmuterError::InvalidInputAccount);
+                    };
+                    require!(
+                        get_asset_id(&merkle_tree.key(), leaf.leaf_info.nonce) == *input_key,
+                        TransmuterError::InvalidInputAccount
+                    );
+
+                    let proof: Vec<&AccountInfo<'info>> =
+                        accounts.by_ref().take(leaf.proof_len as usize).collect();
+                    require!(
+                        proof.len() == leaf.proof_len as usize,
+                        TransmuterError::InvalidInputAccount
+                    );
+
+                    if is_burned {
+                        self.burn_cnft(tree_config, merkle_tree, &leaf.leaf_info, &proof)?;
+                    } else {
+                        self.return_cnft(tree_config, merkle_tree, &leaf.leaf_info, &proof)?;
+                    }
+                }
+                _ => return err!(TransmuterError::InvalidInputAccount),
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn return_token(&self, vault: &AccountInfo<'info>, ata: &AccountInfo<'info>) -> Result<()> {
+        let vault_seed_bytes = self.vault_seed.to_le_bytes();
+        let seeds = &[
+            &b"vaultAuth"[..],
+            &self.transmuter.to_bytes()[..],
+            &self.user.key().to_bytes()[..],
+            &vault_seed_bytes[..],
+            &[self.vault_auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: vault.clone(),
+            to: ata.clone(),
+            authority: self.vault_auth.clone(),
+        };
+
+        let cpi_program = self.token_program.clone();
+        transfer(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds),
+            1,
+        )?;
+
+        //Vault rent goes back to the user who paid it
+        let cpi_accounts = CloseAccount {
+            account: vault.clone(),
+            destination: self.user.clone(),
+            authority: self.vault_auth.clone(),
+        };
+
+        let cpi_program = self.token_program.clone();
+        close_account(CpiContext::new_with_signer(
+            cpi_program,
+            cpi_accounts,
+            signer_seeds,
+        ))
+    }
+
+    pub fn burn_token(&self, mint: &AccountInfo<'info>, vault: &AccountInfo<'info>) -> Result<()> {
+        let vault_seed_bytes = self.vault_seed.to_le_bytes();
+        let seeds = &[
+            &b"vaultAuth"[..],
+            &self.transmuter.to_bytes()[..],
+            &self.user.key().to_bytes()[..],
+            &vault_seed_bytes[..],
+            &[self.vault_auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Burn {
+            mint: mint.clone(),
+            from: vault.clone(),
+            authority: self.vault_auth.clone(),
+        };
+
+        let cpi_program = self.token_program.clone();
+        burn(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds),
+            1,
+        )?;
+
+        let cpi_accounts = CloseAccount {
+            account: vault.clone(),
+            destination: self.user.clone(),
+            authority: self.vault_auth.clone(),
+        };
+
+        let cpi_program = self.token_program.clone();
+        close_account(CpiContext::new_with_signer(
+            cpi_program,
+            cpi_accounts,
+            signer_seeds,
+        ))
+    }
+
+    pub fn return_core(
+        &self,
+        asset: &AccountInfo<'info>,
+        collection: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let core_program = self
+            .core_program
+            .as_ref()
+            .ok_or(TransmuterError::InvalidInputAccount)?;
+
+        let vault_seed_bytes = self.vault_seed.to_le_bytes();
+        let seeds = &[
+            &b"vaultAuth"[..],
+            &self.transmuter.to_bytes()[..],
+            &self.user.key().to_bytes()[..],
+            &vault_seed_bytes[..],
+            &[self.vault_auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let ix = core_transfer_ix(
+            asset.key(),
+            Some(collection.key()),
+            self.payer.key(),
+            self.vault_auth.key(),
+            self.recipient.key(),
+        );
+
+        let account_infos = vec![
+            asset.clone(),
+            collection.clone(),
+            self.payer.clone(),
+            self.vault_auth.clone(),
+            self.recipient.clone(),
+            core_program.clone(),
+        ];
+
+        invoke_signed(&ix, &account_infos, signer_seeds)?;
+        Ok(())
+    }
+
+    pub fn return_cnft(
+        &self,
+        tree_config: &AccountInfo<'info>,
+        merkle_tree: &AccountInfo<'info>,
+        leaf_info: &LeafInfo,
+        proof: &[&AccountInfo<'info>],
+    ) -> Result<()> {
+        let (Some(bubblegum_program), Some(log_wrapper), Some(compression_program)) = (
+            self.bubblegum_program.as_ref(),
+            self.log_wrapper.as_ref(),
+            self.compression_program.as_ref(),
+        ) else {
+            return err!(TransmuterError::InvalidInputAccount);
+        };
+
+        let vault_seed_bytes = self.vault_seed.to_le_bytes();
+        let seeds = &[
+            &b"vaultAuth"[..],
+            &self.transmuter.to_bytes()[..],
+            &self.user.key().to_bytes()[..],
+            &vault_seed_bytes[..],
+            &[self.vault_auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let proof_accounts: Vec<(&AccountInfo<'info>, bool, bool)> =
+            proof.iter().map(|&node| (node, false, false)).collect();
+
+        TransferCpiBuilder::new(bubblegum_program)
+            .tree_config(tree_config)
+            .leaf_owner(&self.vault_auth, true)
+            .leaf_delegate(&self.vault_auth, false)
+            .new_leaf_owner(&self.recipient)
+            .merkle_tree(merkle_tree)
+            .log_wrapper(log_wrapper)
+            .compression_program(compression_program)
+            .system_program(&self.system_program)
+            .root(leaf_info.root)
+            .data_hash(leaf_info.data_hash)
+            .creator_hash(leaf_info.creator_hash)
+            .nonce(leaf_info.nonce)
+            .index(leaf_info.index)
+            .add_remaining_accounts(&proof_accounts)
+            .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+    pub fn burn_core(
+        &self,
+        asset: &AccountInfo<'info>,
+        collection: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let core_program = self
+            .core_program
+            .as_ref()
+            .ok_or(TransmuterError::InvalidInputAccount)?;
+
+        let vault_seed_bytes = self.vault_seed.to_le_bytes();
+        let seeds = &[
+            &b"vaultAuth"[..],
+            &self.transmuter.to_bytes()[..],
+            &self.user.key().to_bytes()[..],
+            &vault_seed_bytes[..],
+            &[self.vault_auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let ix = core_burn_ix(
+            asset.key(),
+            Some(collection.key()),
+            self.payer.key(),
+            self.vault_auth.key(),
+        );
+
+        let account_infos = vec![
+            asset.clone(),
+            collection.clone(),
+            self.payer.clone(),
+            self.vault_auth.clone(),
+            core_program.clone(),
+        ];
+
+        invoke_signed(&ix, &account_infos, signer_seeds)?;
+        Ok(())
+    }
+
+    pub fn burn_cnft(
+        &self,
+        tree_config: &AccountInfo<'info>,
+        merkle_tree: &AccountInfo<'info>,
+        leaf_info: &LeafInfo,
+        proof: &[&AccountInfo<'info>],
+    ) -> Result<()> {
+        let (Some(bubblegum_program), Some(log_wrapper), Some(compression_program)) = (
+            self.bubblegum_program.as_ref(),
+            self.log_wrapper.as_ref(),
+            self.compression_program.as_ref(),
+        ) else {
+            return err!(TransmuterError::InvalidInputAccount);
+        };
+
+        let vault_seed_bytes = self.vault_seed.to_le_bytes();
+        let seeds = &[
+            &b"vaultAuth"[..],
+            &self.transmuter.to_bytes()[..],
+            &self.user.key().to_bytes()[..],
+            &vault_seed_bytes[..],
+            &[self.vault_auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let proof_accounts: Vec<(&AccountInfo<'info>, bool, bool)> =
+            proof.iter().map(|&node| (node, false, false)).collect();
+
+        BurnCpiBuilder::new(bubblegum_program)
+            .tree_config(tree_config)
+            .leaf_owner(&self.vault_auth, true)
+            .leaf_delegate(&self.vault_auth, false)
+            .merkle_tree(merkle_tree)
+            .log_wrapper(log_wrapper)
+            .compression_program(compression_program)
+            .system_program(&self.system_program)
+            .root(leaf_info.root)
+            .data_hash(leaf_info.data_hash)
+            .creator_hash(leaf_info.creator_hash)
+            .nonce(leaf_info.nonce)
+            .index(leaf_info.index)
+            .add_remaining_accounts(&proof_accounts)
+            .invoke_signed(signer_seeds)?;
+
+        Ok(())
+    }
+}
@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
+use mpl_bubblegum::types::MetadataArgs;
 use mpl_token_metadata::accounts::Metadata;
 use url::Url;
 
-use crate::{InputInfo, Rule};
+use crate::{CoreAsset, InputInfo, Rule, MPL_CORE_ID};
 
 pub fn is_matching_nft<'info>(
     metadata: &AccountInfo<'info>,
@@ -13,6 +14,10 @@ pub fn is_matching_nft<'info>(
 
     match input_info.token_standard.as_str() {
         "nft" => {
+            if *metadata.owner != mpl_token_metadata::ID {
+                return Ok(false);
+            }
+
             let input_metadata = Metadata::try_from(metadata)?;
             let collection_pubkey = input_metadata.collection.unwrap().key;
 
@@ -37,15 +42,7 @@ pub fn is_matching_nft<'info>(
                                 parsed_url.query_pairs().into_owned().collect();
 
                             //verify NFT traits
-                            is_match = rule.trait_types.clone().into_iter().all(
-                                |(trait_key, trait_value)| {
-                                    hash_query.clone().into_iter().any(|(key, value)| {
-                                        &trait_key == &key
-                                            && (&trait_value == &value
-                                                || &trait_value == &String::from("*"))
-                                    })
-                                },
-                            );
+                            is_match = is_matching_traits(rule, &hash_query);
                         }
                     }
                 } else {
@@ -53,12 +50,64 @@ pub fn is_matching_nft<'info>(
                 }
             }
         }
+        "core" => {
+            if *metadata.owner != MPL_CORE_ID {
+                return Ok(false);
+            }
+
+            let input_asset = CoreAsset::try_from(metadata)?;
+            is_match = input_asset
+                .collection()
+                .is_some_and(|collection| collection.to_string() == input_info.collection);
+
+            if let (true, Some(rule)) = (is_match, input_info.rule.as_ref()) {
+                //verify asset attributes
+                is_match = rule.name == "traits"
+                    && rule.rule_type == "match"
+                    && is_matching_traits(rule, &input_asset.attributes);
+            }
+        }
         _ => msg!("Token standard not found"),
     };
 
     Ok(is_match)
 }
 
+pub fn is_matching_cnft(metadata: &MetadataArgs, input_info: &InputInfo) -> bool {
+    if input_info.token_standard != "cnft" {
+        return false;
+    }
+
+    let is_collection_match = metadata.collection.as_ref().is_some_and(|collection| {
+        collection.verified && collection.key.to_string() == input_info.collection
+    });
+    if !is_collection_match {
+        return false;
+    }
+
+    match input_info.rule.as_ref() {
+        Some(rule) if rule.name == "traits" && rule.rule_type == "match" => {
+            let Ok(parsed_url) = Url::parse(&metadata.uri) else {
+                return false;
+            };
+            let hash_query: Vec<_> = parsed_url.query_pairs().into_owned().collect();
+
+            //verify cNFT traits
+            is_matching_traits(rule, &hash_query)
+        }
+        Some(_) => false,
+        None => true,
+    }
+}
+
+pub fn is_matching_traits(rule: &Rule, traits: &[(String, String)]) -> bool {
+    rule.trait_types.iter().all(|(trait_key, trait_value)| {
+        traits
+            .iter()
+            .any(|(key, value)| trait_key == key && (trait_value == value || trait_value == "*"))
+    })
+}
+
 pub fn get_matching_traits(input_uri: String, rule: &Rule) -> Vec<(String, String)> {
     let parsed_url = Url::parse(&input_uri).unwrap();
     let hash_query: Vec<_> = parsed_url.query_pairs().into_owned().collect();
@@ -2,8 +2,14 @@ pub mod matcher;
 pub mod parser;
 pub mod formater;
 pub mod handler;
+pub mod core_cpi;
+pub mod output_cpi;
+pub mod input_cpi;
 
 pub use matcher::*;
 pub use parser::*;
 pub use formater::*;
 pub use handler::*;
+pub use core_cpi::*;
+pub use output_cpi::*;
+pub use input_cpi::*;
@@ -0,0 +1,204 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{mint_to, transfer, MintTo, Transfer};
+
+use mpl_token_metadata::instructions::{
+    CreateMasterEditionV3CpiBuilder, CreateMetadataAccountV3CpiBuilder, UpdateV1CpiBuilder,
+    VerifyCreatorV1CpiBuilder,
+};
+use mpl_token_metadata::types::{Collection, Creator, DataV2};
+
+//Accounts shared by every output minted or transferred from remaining accounts
+pub struct OutputCpi<'info> {
+    pub transmuter: Pubkey,
+    pub auth_bump: u8,
+    pub creator: AccountInfo<'info>,
+    pub user: AccountInfo<'info>,
+    pub auth: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub token_metadata_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+    pub sysvar_instructions: AccountInfo<'info>,
+}
+
+impl<'info> OutputCpi<'info> {
+    pub fn mint_token(&self, mint: &AccountInfo<'info>, ata: &AccountInfo<'info>) -> Result<()> {
+        self.mint_to_user(mint, ata, 1)
+    }
+
+    pub fn mint_to_user(
+        &self,
+        mint: &AccountInfo<'info>,
+        ata: &AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        let seeds = &[
+            &b"auth"[..],
+            &self.transmuter.to_bytes()[..],
+            &[self.auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = MintTo {
+            mint: mint.clone(),
+            to: ata.clone(),
+            authority: self.auth.clone(),
+        };
+
+        let mint_ctx =
+            CpiContext::new_with_signer(self.token_program.clone(), cpi_accounts, signer_seeds);
+
+        mint_to(mint_ctx, amount)
+    }
+
+    pub fn transfer_from_auth(
+        &self,
+        auth_ata: &AccountInfo<'info>,
+        ata: &AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        let seeds = &[
+            &b"auth"[..],
+            &self.transmuter.to_bytes()[..],
+            &[self.auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: auth_ata.clone(),
+            to: ata.clone(),
+            authority: self.auth.clone(),
+        };
+
+        let cpi_program = self.token_program.clone();
+
+        transfer(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds),
+            amount,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_metadata(
+        &self,
+        mint: &AccountInfo<'info>,
+        metadata: &AccountInfo<'info>,
+        title: &str,
+        symbol: &str,
+        uri: &str,
+        collection_mint: &Option<String>,
+        seller_fee_basis_point: u16,
+    ) -> Result<()> {
+        let seeds = &[
+            &b"auth"[..],
+            &self.transmuter.to_bytes()[..],
+            &[self.auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let auth_creator = Creator {
+            address: self.auth.key(),
+            verified: false,
+            share: 0,
+        };
+        let creator = Creator {
+            address: self.creator.key(),
+            verified: false,
+            share: 100,
+        };
+
+        let collection = collection_mint
+            .as_ref()
+            .map(|collection_string| Collection {
+                key: Pubkey::from_str(collection_string).unwrap(),
+                verified: false,
+            });
+
+        let data: DataV2 = DataV2 {
+            name: title.to_string(),
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+            seller_fee_basis_points: seller_fee_basis_point,
+            creators: Some(vec![auth_creator, creator]),
+            collection,
+            uses: None,
+        };
+
+        CreateMetadataAccountV3CpiBuilder::new(&self.token_metadata_program)
+            .metadata(metadata)
+            .mint(mint)
+            .mint_authority(&self.auth.clone())
+            .payer(&self.user.clone())
+            .update_authority(&self.auth.clone(), true)
+            .system_program(&self.system_program)
+            .rent(Some(&self.rent))
+            .data(data)
+            .is_mutable(true)
+            .invoke_signed(signer_seeds)?;
+
+        let result = VerifyCreatorV1CpiBuilder::new(&self.token_metadata_program)
+            .authority(&self.auth.clone())
+            .metadata(metadata)
+            .system_program(&self.system_program)
+            .sysvar_instructions(&self.sysvar_instructions.clone())
+            .add_remaining_account(&self.token_metadata_program.clone(), false, false)
+            .invoke_signed(signer_seeds);
+
+        Ok(result?)
+    }
+
+    pub fn create_master_edition(
+        &self,
+        mint: &AccountInfo<'info>,
+        metadata: &AccountInfo<'info>,
+        master_edition: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let seeds = &[
+            &b"auth"[..],
+            &self.transmuter.to_bytes()[..],
+            &[self.auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let result = CreateMasterEditionV3CpiBuilder::new(&self.token_metadata_program)
+            .edition(master_edition)
+            .mint(mint)
+            .update_authority(&self.auth.clone())
+            .mint_authority(&self.auth.clone())
+            .payer(&self.user.clone())
+            .metadata(metadata)
+            .max_supply(1)
+            .token_program(&self.token_program)
+            .system_program(&self.system_program)
+            .rent(Some(&self.rent))
+            .invoke_signed(signer_seeds);
+
+        Ok(result?)
+    }
+
+    pub fn update_authority(
+        &self,
+        mint: &AccountInfo<'info>,
+        metadata: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let seeds = &[
+            &b"auth"[..],
+            &self.transmuter.to_bytes()[..],
+            &[self.auth_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let result = UpdateV1CpiBuilder::new(&self.token_metadata_program)
+            .authority(&self.auth.clone())
+            .mint(mint)
+            .metadata(metadata)
+            .new_update_authority(self.creator.key())
+            .payer(&self.user.clone())
+            .system_program(&self.system_program.clone())
+            .sysvar_instructions(&self.sysvar_instructions.clone())
+            .invoke_signed(signer_seeds);
+
+        Ok(result?)
+    }
+}
@@ -2,13 +2,18 @@ use anchor_lang::error::Error;
 use serde::Deserialize;
 use solana_program::msg;
 
+use crate::TransmuterError;
+
 pub fn parse_json_vec<'a, T>(json_vec: &'a Vec<String>) -> Result<Vec<T>, Error>
 where
     T: Deserialize<'a>,
 {
     let mut result = Vec::new();
     for index in 0..json_vec.len() {
-        result.push(serde_json::from_str::<T>(&json_vec[index]).unwrap())
+        result.push(
+            serde_json::from_str::<T>(&json_vec[index])
+                .map_err(|_| TransmuterError::JSONParseError)?,
+        )
     }
 
     Ok(result)
@@ -18,6 +23,7 @@ pub fn parse_json<'a, T>(json_vec: &'a String) -> Result<T, Error>
 where
     T: Deserialize<'a>,
 {
-    let result = serde_json::from_str::<T>(&json_vec).unwrap();
+    let result =
+        serde_json::from_str::<T>(&json_vec).map_err(|_| TransmuterError::JSONParseError)?;
     Ok(result)
 }
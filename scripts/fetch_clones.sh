@@ -0,0 +1,24 @@
+#!/usr/bin/env bash
+# Dumps the mainnet programs the local validator loads into clones/.
+# The .so files are gitignored, run this once before `anchor test` or `yarn validator`.
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+mkdir -p clones
+
+programs=(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s metaplex"
+  "CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d mpl_core"
+  "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY mpl_bubblegum"
+  "cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK spl_account_compression"
+  "noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV spl_noop"
+)
+
+for program in "${programs[@]}"; do
+  read -r address name <<<"$program"
+  if [[ -f "clones/$name.so" && "${1:-}" != "--force" ]]; then
+    echo "clones/$name.so already there, skipping (--force to refetch)"
+    continue
+  fi
+  solana program dump -u m "$address" "clones/$name.so"
+done
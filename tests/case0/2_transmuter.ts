@@ -194,6 +194,8 @@ it("should close an existing transmuter", async () => {
     .accounts({
       creator: creator.publicKey,
       transmuter,
+      auth,
+      tokenProgram,
     })
     .signers([creator])
     .rpc({
@@ -248,7 +250,7 @@ it("should fail to handle input", async () => {
     const metadata = await getMetadata(inputMint);
 
     await program.methods
-      .userSendInput(seed, vaultSeed)
+      .userSendInput(seed, vaultSeed, null)
       .accounts({
         creator: creator.publicKey,
         user: user.publicKey,
@@ -386,6 +388,333 @@ it("should add one output to the transmuter", async () => {
     });
 });
 
+it("should fail to apply a recipe change that was never staged", async () => {
+  try {
+    await program.methods
+      .transmuterApplyRecipe(seed)
+      .accounts({
+        creator: creator.publicKey,
+        transmuter,
+      })
+      .signers([creator])
+      .rpc({
+        skipPreflight: true,
+      });
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("checks the recipe was applied without a pending change", async () => {
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+
+  assert.equal(transmuterStruct.account.pendingEffectiveAt, null);
+  assert.equal(transmuterStruct.account.inputs.length, 1);
+  assert.equal(transmuterStruct.account.outputs.length, 1);
+});
+
+it("should publish the transmuter", async () => {
+  await program.methods
+    .transmuterPublish(seed, false)
+    .accounts({
+      creator: creator.publicKey,
+      transmuter,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+
+  assert.ok(transmuterStruct.account.immutable);
+  assert.ok(!transmuterStruct.account.configFrozen);
+});
+
+it("should fail to add an input to a published transmuter", async () => {
+  try {
+    const inputInfo = {
+      token_standard: "nft",
+      collection: inputCollection.nft.address.toBase58(),
+      method: "burn",
+      amount: 1,
+    };
+
+    await program.methods
+      .transmuterSetInput(seed, JSON.stringify(inputInfo))
+      .accounts({
+        creator: creator.publicKey,
+        transmuter,
+      })
+      .signers([creator])
+      .rpc({
+        skipPreflight: true,
+      });
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should fail to change a frozen config field on a published transmuter", async () => {
+  try {
+    const transmuterConfig = {
+      input_length: 2,
+      output_length: 2,
+      transmute_max: 1,
+      traits_uri: "https://example.com/traits.json",
+    };
+
+    await program.methods
+      .transmuterSet(seed, JSON.stringify(transmuterConfig))
+      .accounts({
+        creator: creator.publicKey,
+        transmuter,
+      })
+      .signers([creator])
+      .rpc();
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("TransmuterImmutable"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should still update the max transmute on a published transmuter", async () => {
+  const transmuterConfig = {
+    input_length: 2,
+    output_length: 2,
+    transmute_max: 1,
+  };
+
+  await program.methods
+    .transmuterSet(seed, JSON.stringify(transmuterConfig))
+    .accounts({
+      creator: creator.publicKey,
+      transmuter,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+
+  assert.equal(transmuterStruct.account.transmuteMax, 1);
+  assert.equal(transmuterStruct.account.traitsUri, null);
+});
+
+it("should propose the user as the new authority", async () => {
+  await program.methods
+    .transmuterProposeAuthority(seed, user.publicKey)
+    .accounts({
+      creator: creator.publicKey,
+      transmuter,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+
+  assert.equal(
+    transmuterStruct.account.pendingAuthority.toBase58(),
+    user.publicKey.toBase58()
+  );
+});
+
+it("should fail to accept the authority if not proposed", async () => {
+  try {
+    await program.methods
+      .transmuterAcceptAuthority(seed)
+      .accounts({
+        authority: creator.publicKey,
+        transmuter,
+      })
+      .signers([creator])
+      .rpc();
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should hand the authority over and back", async () => {
+  await program.methods
+    .transmuterAcceptAuthority(seed)
+    .accounts({
+      authority: user.publicKey,
+      transmuter,
+    })
+    .signers([user])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+
+  //Seed creator is kept so the transmuter address doesn't move
+  assert.equal(
+    transmuterStruct.account.authority.toBase58(),
+    user.publicKey.toBase58()
+  );
+  assert.equal(
+    transmuterStruct.account.creator.toBase58(),
+    creator.publicKey.toBase58()
+  );
+
+  await program.methods
+    .transmuterProposeAuthority(seed, creator.publicKey)
+    .accounts({
+      creator: user.publicKey,
+      transmuter,
+    })
+    .signers([user])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  await program.methods
+    .transmuterAcceptAuthority(seed)
+    .accounts({
+      authority: creator.publicKey,
+      transmuter,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+});
+
+it("should add the user as a pause operator", async () => {
+  //Role bitflags: 1 pause, 2 edit, 4 resolve, 8 withdraw
+  await program.methods
+    .transmuterSetOperator(seed, user.publicKey, 1)
+    .accounts({
+      creator: creator.publicKey,
+      transmuter,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+
+  assert.equal(transmuterStruct.account.operators.length, 1);
+  assert.equal(transmuterStruct.account.operators[0].roles, 1);
+});
+
+it("should fail for an operator to manage operators", async () => {
+  try {
+    await program.methods
+      .transmuterSetOperator(seed, user.publicKey, 15)
+      .accounts({
+        creator: user.publicKey,
+        transmuter,
+      })
+      .signers([user])
+      .rpc();
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should let the pause operator resume the transmuter", async () => {
+  await program.methods
+    .transmuterResume(seed)
+    .accounts({
+      creator: user.publicKey,
+      transmuter,
+    })
+    .signers([user])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+
+  assert.ok(!transmuterStruct.account.locked);
+});
+
+it("should fail for a pause operator to escrow a sol output", async () => {
+  try {
+    const escrowSeed = new BN(randomBytes(8));
+    const escrow = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("solEscrow"),
+        transmuter.toBytes(),
+        escrowSeed.toBuffer().reverse(),
+      ],
+      program.programId
+    )[0];
+
+    const outputInfo = {
+      token_standard: "sol",
+      method: "transfer",
+      amount: 1000000,
+      escrow: escrow.toBase58(),
+    };
+
+    await program.methods
+      .transmuterSetOutputSol(
+        seed,
+        escrowSeed,
+        JSON.stringify(outputInfo),
+        new BN(1000000)
+      )
+      .accounts({
+        creator: user.publicKey,
+        transmuter,
+        escrow,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("MissingRole"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
 it("should pause the transmuter", async () => {
   await program.methods
     .transmuterPause(seed)
@@ -128,6 +128,8 @@ it("should fail for a user to close a transmuter", async () => {
       .accounts({
         creator: user.publicKey,
         transmuter: transmuter.publicKey,
+        auth,
+        tokenProgram,
       })
       .signers([user])
       .rpc({
@@ -172,6 +174,62 @@ it("should init vault auth", async () => {
     });
 });
 
+it("should have pinned the transmuter recipe", async () => {
+  const transmuter = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+
+  const vaultAuth = await getvaultAuthStruct(
+    program,
+    transmuter.publicKey,
+    user.publicKey,
+    vaultSeed
+  );
+
+  assert.equal(
+    vaultAuth.account.recipeVersion.toString(),
+    transmuter.account.recipeVersion.toString()
+  );
+  assert.deepEqual(
+    vaultAuth.account.recipeHash,
+    transmuter.account.recipeHash
+  );
+  //Claims and resolutions read the pinned recipe, not the live one
+  assert.deepEqual(vaultAuth.account.inputs, transmuter.account.inputs);
+  assert.deepEqual(vaultAuth.account.outputs, transmuter.account.outputs);
+});
+
+it("should sync vault auth", async () => {
+  const transmuter = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+
+  const vaultAuth = await getvaultAuthStruct(
+    program,
+    transmuter.publicKey,
+    user.publicKey,
+    vaultSeed
+  );
+
+  await program.methods
+    .userSyncVault(seed, vaultSeed)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      vaultAuth: vaultAuth.publicKey,
+      transmuter: transmuter.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([user])
+    .rpc({
+      skipPreflight: true,
+    });
+});
+
 it("should pause the transmuter", async () => {
   //Must have creator and seed to find transmuter
   const transmuter = await getTransmuterStruct(
@@ -237,7 +295,7 @@ it("should fail to send input", async () => {
     const metadata = await getMetadata(inputMint);
 
     await program.methods
-      .userSendInput(seed, vaultSeed)
+      .userSendInput(seed, vaultSeed, null)
       .accounts({
         creator: creator.publicKey,
         user: user.publicKey,
@@ -323,7 +381,7 @@ it("should send input", async () => {
   const metadata = await getMetadata(inputMint);
 
   await program.methods
-    .userSendInput(seed, vaultSeed)
+    .userSendInput(seed, vaultSeed, null)
     .accounts({
       creator: creator.publicKey,
       user: user.publicKey,
@@ -341,6 +399,131 @@ it("should send input", async () => {
     });
 });
 
+it("should fail to send the same input twice", async () => {
+  try {
+    const inputMint = inputMints[0].nft.address;
+
+    //Must have creator and seed to find transmuter
+    const transmuter = await getTransmuterStruct(
+      program,
+      creator.publicKey,
+      seed
+    );
+
+    const vaultAuth = await getvaultAuthStruct(
+      program,
+      transmuter.publicKey,
+      user.publicKey,
+      vaultSeed
+    );
+
+    const ata = await getOrCreateAssociatedTokenAccount(
+      anchor.getProvider().connection,
+      user,
+      inputMint,
+      user.publicKey,
+      true
+    );
+
+    const vault = await getOrCreateAssociatedTokenAccount(
+      anchor.getProvider().connection,
+      user,
+      inputMint,
+      vaultAuth.publicKey,
+      true
+    );
+
+    const metadata = await getMetadata(inputMint);
+
+    await program.methods
+      .userSendInput(seed, vaultSeed, null)
+      .accounts({
+        creator: creator.publicKey,
+        user: user.publicKey,
+        mint: inputMint,
+        ata: ata.address,
+        metadata: metadata,
+        vaultAuth: vaultAuth.publicKey,
+        vault: vault.address,
+        tokenProgram,
+        transmuter: transmuter.publicKey,
+      })
+      .signers([user])
+      .rpc({
+        skipPreflight: true,
+      });
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should fail to refund a vault that has not expired", async () => {
+  try {
+    const inputMint = inputMints[0].nft.address;
+
+    const transmuter = await getTransmuterStruct(
+      program,
+      creator.publicKey,
+      seed
+    );
+
+    const vaultAuth = await getvaultAuthStruct(
+      program,
+      transmuter.publicKey,
+      user.publicKey,
+      vaultSeed
+    );
+
+    const ata = await getOrCreateAssociatedTokenAccount(
+      anchor.getProvider().connection,
+      user,
+      inputMint,
+      user.publicKey,
+      true
+    );
+
+    const vault = await getOrCreateAssociatedTokenAccount(
+      anchor.getProvider().connection,
+      user,
+      inputMint,
+      vaultAuth.publicKey,
+      true
+    );
+
+    //Anyone can crank a refund, the creator is only used as a signer here
+    await program.methods
+      .vaultRefund(seed, vaultSeed, [])
+      .accounts({
+        cranker: creator.publicKey,
+        creator: creator.publicKey,
+        user: user.publicKey,
+        vaultAuth: vaultAuth.publicKey,
+        tokenProgram,
+        systemProgram: SystemProgram.programId,
+        coreProgram: null,
+        bubblegumProgram: null,
+        logWrapper: null,
+        compressionProgram: null,
+        transmuter: transmuter.publicKey,
+      })
+      .remainingAccounts([
+        { pubkey: inputMint, isSigner: false, isWritable: false },
+        { pubkey: vault.address, isSigner: false, isWritable: true },
+        { pubkey: ata.address, isSigner: false, isWritable: true },
+      ])
+      .signers([creator])
+      .rpc({
+        skipPreflight: true,
+      });
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
 it("should cancel inputs", async () => {
   const transmuter = await getTransmuterStruct(
     program,
@@ -455,7 +638,7 @@ it("should fail to claim output", async () => {
     const masterEdition = await getMasterEdition(mint);
 
     await program.methods
-      .userClaimOutputNft(seed, vaultSeed)
+      .userClaimOutputNft(seed, vaultSeed, [])
       .accounts({
         creator: creator.publicKey,
         user: user.publicKey,
@@ -521,7 +704,7 @@ it("should send input again", async () => {
   const metadata = await getMetadata(inputMint);
 
   await program.methods
-    .userSendInput(seed, vaultSeed)
+    .userSendInput(seed, vaultSeed, null)
     .accounts({
       creator: creator.publicKey,
       user: user.publicKey,
@@ -605,7 +788,7 @@ it("should fail to claim output", async () => {
     const masterEdition = await getMasterEdition(mint);
 
     await program.methods
-      .userClaimOutputNft(seed, vaultSeed)
+      .userClaimOutputNft(seed, vaultSeed, [])
       .accounts({
         creator: creator.publicKey,
         user: user.publicKey,
@@ -732,7 +915,7 @@ it("should send another input in another vault", async () => {
   const metadata = await getMetadata(inputMint);
 
   await program.methods
-    .userSendInput(seed, vaultSeed2)
+    .userSendInput(seed, vaultSeed2, null)
     .accounts({
       creator: creator.publicKey,
       user: user.publicKey,
@@ -787,7 +970,7 @@ it("should claim output", async () => {
   const masterEdition = await getMasterEdition(mint);
 
   await program.methods
-    .userClaimOutputNft(seed, vaultSeed)
+    .userClaimOutputNft(seed, vaultSeed, [])
     .accounts({
       creator: creator.publicKey,
       user: user.publicKey,
@@ -851,7 +1034,7 @@ it("should fail to claim output from vault 2 as max reached", async () => {
     const masterEdition = await getMasterEdition(mint);
 
     await program.methods
-      .userClaimOutputNft(seed, vaultSeed2)
+      .userClaimOutputNft(seed, vaultSeed2, [])
       .accounts({
         creator: creator.publicKey,
         user: user.publicKey,
@@ -97,7 +97,7 @@ it("Should fail to handle input as another user", async () => {
     const metadata = await getMetadata(inputMint);
 
     await program.methods
-      .userSendInput(seed, vaultSeed)
+      .userSendInput(seed, vaultSeed, null)
       .accounts({
         creator: creator.publicKey,
         user: creator.publicKey,
@@ -156,7 +156,7 @@ it("should handle input", async () => {
   const metadata = await getMetadata(inputMint);
 
   await program.methods
-    .userSendInput(seed, vaultSeed)
+    .userSendInput(seed, vaultSeed, null)
     .accounts({
       creator: creator.publicKey,
       user: user.publicKey,
@@ -214,7 +214,7 @@ it("Should fail to handle input as trait is not matching", async () => {
     const metadata = await getMetadata(inputMint);
 
     await program.methods
-      .userSendInput(seed, vaultSeed)
+      .userSendInput(seed, vaultSeed, null)
       .accounts({
         creator: creator.publicKey,
         user: user.publicKey,
@@ -277,7 +277,7 @@ it("should fail to claim output", async () => {
     const masterEdition = await getMasterEdition(mint);
 
     await program.methods
-      .userClaimOutputNft(seed, vaultSeed)
+      .userClaimOutputNft(seed, vaultSeed, [])
       .accounts({
         creator: creator.publicKey,
         user: user.publicKey,
@@ -365,7 +365,7 @@ it("should handle input", async () => {
   const metadata = await getMetadata(inputMint);
 
   await program.methods
-    .userSendInput(seed, vaultSeed)
+    .userSendInput(seed, vaultSeed, null)
     .accounts({
       creator: creator.publicKey,
       user: user.publicKey,
@@ -440,7 +440,7 @@ it("should claim output", async () => {
   const masterEdition = await getMasterEdition(mint);
 
   await program.methods
-    .userClaimOutputNft(seed, vaultSeed)
+    .userClaimOutputNft(seed, vaultSeed, [])
     .accounts({
       creator: creator.publicKey,
       user: user.publicKey,
@@ -542,6 +542,7 @@ it("should verify that creator cannot resolve incomplete transmuter", async () =
                     vaultAuth.account.seed
                   )
                   .accounts({
+                    cranker: creator.publicKey,
                     creator: creator.publicKey,
                     user: user.publicKey,
                     mint: vaultAuthNft.mintAddress,
@@ -572,6 +573,7 @@ it("should verify that creator cannot resolve incomplete transmuter", async () =
                     vaultAuth.account.seed
                   )
                   .accounts({
+                    cranker: creator.publicKey,
                     creator: creator.publicKey,
                     user: user.publicKey,
                     mint: vaultAuthNft.mintAddress,
@@ -638,7 +640,7 @@ it("should claim output", async () => {
   const masterEdition = await getMasterEdition(mint);
 
   await program.methods
-    .userClaimOutputNft(seed, vaultSeed)
+    .userClaimOutputNft(seed, vaultSeed, [])
     .accounts({
       creator: creator.publicKey,
       user: user.publicKey,
@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Metadata } from "@metaplex-foundation/js";
 import { creator, creatorMetaplex, user } from "./1_init";
 import {
+  RESOLVE_TIP_LAMPORTS,
   confirmTx,
   getMetadata,
   getTransmuterStruct,
@@ -9,7 +10,7 @@ import {
   getvaultAuthStruct,
   getvaultAuthStructs,
 } from "../utils";
-import { SystemProgram } from "@solana/web3.js";
+import { Keypair, SystemProgram } from "@solana/web3.js";
 import {
   getOrCreateAssociatedTokenAccount,
   TOKEN_PROGRAM_ID as tokenProgram,
@@ -19,203 +20,146 @@ import { program } from "..";
 import { seed } from "./2_transmuter";
 import { vaultSeed } from "./3_user";
 
-it("should not be possible for a user to resolve an input", async () => {
-  try {
-    const transmuters = await getTransmuterStructs(program, creator.publicKey);
+const setCreatorResolveOnly = async (creatorResolveOnly: boolean) => {
+  const transmuter = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
 
-    for (let transmuter of transmuters) {
-      const vaultAuthStructs = await getvaultAuthStructs(
-        program,
-        transmuter.publicKey,
-        false
-      );
+  const transmuterConfig = {
+    input_length: 2,
+    output_length: 2,
+    transmute_max: 1,
+    creator_resolve_only: creatorResolveOnly,
+  };
+
+  await program.methods
+    .transmuterSet(seed, JSON.stringify(transmuterConfig))
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: transmuter.publicKey,
+    })
+    .signers([creator])
+    .rpc()
+    .then(confirmTx);
+};
+
+//Resolves the first input of the open vault with the given cranker
+const resolveFirstInput = async (cranker: Keypair) => {
+  const transmuter = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
 
-      const vaultAuth = vaultAuthStructs[0];
+  const vaultAuthStructs = await getvaultAuthStructs(
+    program,
+    transmuter.publicKey,
+    false
+  );
 
-      const vaultAuthNfts = (await creatorMetaplex
-        .nfts()
-        .findAllByOwner({ owner: vaultAuth.publicKey })) as Metadata[];
+  const vaultAuth = vaultAuthStructs[0];
 
-      const vaultAuthNft = vaultAuthNfts[0];
+  const vaultAuthNfts = (await creatorMetaplex
+    .nfts()
+    .findAllByOwner({ owner: vaultAuth.publicKey })) as Metadata[];
 
-      const vault = await getOrCreateAssociatedTokenAccount(
-        anchor.getProvider().connection,
-        creator,
-        vaultAuthNft.mintAddress,
-        vaultAuth.publicKey,
-        true
-      );
+  const vaultAuthNft = vaultAuthNfts[0];
 
-      const metadata = await getMetadata(vaultAuthNft.mintAddress);
+  const vault = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    creator,
+    vaultAuthNft.mintAddress,
+    vaultAuth.publicKey,
+    true
+  );
 
-      const inputInfoIndex = vaultAuth.account.handledInputs.findIndex(
-        (inputAddress) =>
-          inputAddress?.toBase58() === vaultAuthNft.mintAddress.toBase58()
-      );
+  const inputInfoIndex = vaultAuth.account.handledInputs.findIndex(
+    (inputAddress) =>
+      inputAddress?.toBase58() === vaultAuthNft.mintAddress.toBase58()
+  );
 
-      const inputInfo = JSON.parse(transmuter.account.inputs[inputInfoIndex]);
+  const inputInfo = JSON.parse(transmuter.account.inputs[inputInfoIndex]);
+
+  if (inputInfo.method === "burn") {
+    await program.methods
+      .creatorBurnInput(transmuter.account.seed, vaultAuth.account.seed)
+      .accounts({
+        cranker: cranker.publicKey,
+        creator: creator.publicKey,
+        user: user.publicKey,
+        mint: vaultAuthNft.mintAddress,
+        vaultAuth: vaultAuth.publicKey,
+        vault: vault.address,
+        tokenProgram,
+        transmuter: transmuter.publicKey,
+      })
+      .signers([cranker])
+      .rpc()
+      .then(confirmTx);
+    return;
+  }
 
-      if (inputInfo) {
-        switch (inputInfo.method) {
-          case "burn":
-            {
-              await program.methods
-                .creatorBurnInput(
-                  transmuter.account.seed,
-                  vaultAuth.account.seed
-                )
-                .accounts({
-                  creator: user.publicKey,
-                  user: user.publicKey,
-                  mint: vaultAuthNft.mintAddress,
-                  vaultAuth: vaultAuth.publicKey,
-                  vault: vault.address,
-                  tokenProgram,
-                  transmuter: transmuter.publicKey,
-                })
-                .signers([user])
-                .rpc({
-                  skipPreflight: true,
-                });
-            }
-            break;
-          case "transfer":
-            {
-              const creatorAta = await getOrCreateAssociatedTokenAccount(
-                anchor.getProvider().connection,
-                creator,
-                vaultAuthNft.mintAddress,
-                creator.publicKey,
-                true
-              );
+  const creatorAta = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    creator,
+    vaultAuthNft.mintAddress,
+    creator.publicKey,
+    true
+  );
 
-              await program.methods
-                .creatorResolveInput(
-                  transmuter.account.seed,
-                  vaultAuth.account.seed
-                )
-                .accounts({
-                  creator: user.publicKey,
-                  user: user.publicKey,
-                  mint: vaultAuthNft.mintAddress,
-                  ata: creatorAta.address,
-                  vaultAuth: vaultAuth.publicKey,
-                  vault: vault.address,
-                  tokenProgram,
-                  transmuter: transmuter.publicKey,
-                })
-                .signers([user])
-                .rpc({
-                  skipPreflight: true,
-                })
-                .then(confirmTx);
-            }
-            break;
-          default:
-            console.log("Method not found");
-        }
-      }
-    }
+  await program.methods
+    .creatorResolveInput(transmuter.account.seed, vaultAuth.account.seed)
+    .accounts({
+      cranker: cranker.publicKey,
+      creator: creator.publicKey,
+      user: user.publicKey,
+      mint: vaultAuthNft.mintAddress,
+      ata: creatorAta.address,
+      vaultAuth: vaultAuth.publicKey,
+      vault: vault.address,
+      tokenProgram,
+      transmuter: transmuter.publicKey,
+    })
+    .signers([cranker])
+    .rpc()
+    .then(confirmTx);
+};
+
+it("should not be possible for a user to resolve an input", async () => {
+  await setCreatorResolveOnly(true);
+
+  try {
+    await resolveFirstInput(user);
   } catch (e) {
     assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("CreatorResolveOnly"));
     return;
   }
   assert.fail("Test should have failed");
 });
 
-it("resolves an input", async () => {
-  const transmuters = await getTransmuterStructs(program, creator.publicKey);
-
-  for (let transmuter of transmuters) {
-    const vaultAuthStructs = await getvaultAuthStructs(
-      program,
-      transmuter.publicKey,
-      false
-    );
-
-    const vaultAuth = vaultAuthStructs[0];
-
-    const vaultAuthNfts = (await creatorMetaplex
-      .nfts()
-      .findAllByOwner({ owner: vaultAuth.publicKey })) as Metadata[];
-
-    const vaultAuthNft = vaultAuthNfts[0];
+it("resolves an input with any cranker and tips it", async () => {
+  await setCreatorResolveOnly(false);
 
-    const vault = await getOrCreateAssociatedTokenAccount(
-      anchor.getProvider().connection,
-      creator,
-      vaultAuthNft.mintAddress,
-      vaultAuth.publicKey,
-      true
-    );
+  //Fees are paid by the provider wallet, so the balance only moves by the tip
+  const cranker = new Keypair();
+  await anchor
+    .getProvider()
+    .connection.requestAirdrop(cranker.publicKey, anchor.web3.LAMPORTS_PER_SOL)
+    .then(confirmTx);
 
-    const metadata = await getMetadata(vaultAuthNft.mintAddress);
+  const balanceBefore = await anchor
+    .getProvider()
+    .connection.getBalance(cranker.publicKey);
 
-    const inputInfoIndex = vaultAuth.account.handledInputs.findIndex(
-      (inputAddress) =>
-        inputAddress?.toBase58() === vaultAuthNft.mintAddress.toBase58()
-    );
+  await resolveFirstInput(cranker);
 
-    const inputInfo = JSON.parse(transmuter.account.inputs[inputInfoIndex]);
-
-    if (inputInfo) {
-      switch (inputInfo.method) {
-        case "burn":
-          {
-            await program.methods
-              .creatorBurnInput(transmuter.account.seed, vaultAuth.account.seed)
-              .accounts({
-                creator: creator.publicKey,
-                user: user.publicKey,
-                mint: vaultAuthNft.mintAddress,
-                vaultAuth: vaultAuth.publicKey,
-                vault: vault.address,
-                tokenProgram,
-                transmuter: transmuter.publicKey,
-              })
-              .signers([creator])
-              .rpc({
-                skipPreflight: true,
-              });
-          }
-          break;
-        case "transfer":
-          {
-            const creatorAta = await getOrCreateAssociatedTokenAccount(
-              anchor.getProvider().connection,
-              creator,
-              vaultAuthNft.mintAddress,
-              creator.publicKey,
-              true
-            );
-
-            await program.methods
-              .creatorResolveInput(
-                transmuter.account.seed,
-                vaultAuth.account.seed
-              )
-              .accounts({
-                creator: creator.publicKey,
-                user: user.publicKey,
-                mint: vaultAuthNft.mintAddress,
-                ata: creatorAta.address,
-                vaultAuth: vaultAuth.publicKey,
-                vault: vault.address,
-                tokenProgram,
-                transmuter: transmuter.publicKey,
-              })
-              .signers([creator])
-              .rpc({
-                skipPreflight: true,
-              })
-              .then(confirmTx);
-          }
-          break;
-        default:
-          console.log("Method not found");
-      }
-    }
-  }
+  const balanceAfter = await anchor
+    .getProvider()
+    .connection.getBalance(cranker.publicKey);
+  assert.equal(balanceAfter - balanceBefore, RESOLVE_TIP_LAMPORTS);
 });
 
 it("should check that the inputs are not all resolved", async () => {
@@ -297,6 +241,7 @@ it("resolves all inputs", async () => {
                   vaultAuth.account.seed
                 )
                 .accounts({
+                  cranker: creator.publicKey,
                   creator: creator.publicKey,
                   user: user.publicKey,
                   mint: vaultAuthNft.mintAddress,
@@ -327,6 +272,7 @@ it("resolves all inputs", async () => {
                   vaultAuth.account.seed
                 )
                 .accounts({
+                  cranker: creator.publicKey,
                   creator: creator.publicKey,
                   user: user.publicKey,
                   mint: vaultAuthNft.mintAddress,
@@ -6,6 +6,7 @@ import {
   confirmTx,
   getMasterEdition,
   getMetadata,
+  getInputUris,
   getTransmuterStruct,
   getvaultAuthStruct,
   modifyComputeUnits,
@@ -95,7 +96,7 @@ it("should handle input", async () => {
   const metadata = await getMetadata(inputMint);
 
   await program.methods
-    .userSendInput(seed, vaultSeed)
+    .userSendInput(seed, vaultSeed, null)
     .accounts({
       creator: creator.publicKey,
       user: user.publicKey,
@@ -129,6 +130,11 @@ it("should claim outputs", async () => {
     vaultSeed
   );
 
+  const inputUris = await getInputUris(
+    userMetaplex,
+    vaultAuth.account.handledInputs
+  );
+
   for (let i = 0; i < transmuter.account.outputs.length; i++) {
     let mint = await createMint(
       anchor.getProvider().connection,
@@ -150,7 +156,7 @@ it("should claim outputs", async () => {
 
     const masterEdition = await getMasterEdition(mint);
     await program.methods
-      .userClaimOutputNft(seed, vaultSeed)
+      .userClaimOutputNft(seed, vaultSeed, inputUris)
       .accounts({
         creator: creator.publicKey,
         user: user.publicKey,
@@ -56,7 +56,9 @@ it("resolves all inputs", async () => {
                   transmuter.account.seed,
                   vaultAuth.account.seed
                 )
+                //Burn is permissionless, any signer can crank it
                 .accounts({
+                  cranker: user.publicKey,
                   creator: creator.publicKey,
                   user: user.publicKey,
                   mint: vaultAuthNft.mintAddress,
@@ -65,7 +67,7 @@ it("resolves all inputs", async () => {
                   tokenProgram,
                   transmuter: transmuter.publicKey,
                 })
-                .signers([creator])
+                .signers([user])
                 .rpc({
                   skipPreflight: true,
                 });
@@ -87,6 +89,7 @@ it("resolves all inputs", async () => {
                   vaultAuth.account.seed
                 )
                 .accounts({
+                  cranker: creator.publicKey,
                   creator: creator.publicKey,
                   user: user.publicKey,
                   mint: vaultAuthNft.mintAddress,
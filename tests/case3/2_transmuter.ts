@@ -14,6 +14,7 @@ import {
 import { creator, inputCollection, creatorMint } from "./1_init";
 import { program, programId } from "..";
 import {
+  getAccount,
   getOrCreateAssociatedTokenAccount,
   TOKEN_PROGRAM_ID as tokenProgram,
 } from "@solana/spl-token";
@@ -184,6 +185,7 @@ it("should set one spl output to the transmuter", async () => {
       creator: creator.publicKey,
       transmuter,
       creatorAta: creatorAta.address,
+      mint: creatorMint,
       authAta: authAta.address,
       tokenProgram,
     })
@@ -227,6 +229,7 @@ it("should fail to set one spl output with too much token", async () => {
         creator: creator.publicKey,
         transmuter,
         creatorAta: creatorAta.address,
+        mint: creatorMint,
         authAta: authAta.address,
         tokenProgram,
       })
@@ -241,72 +244,13 @@ it("should fail to set one spl output with too much token", async () => {
   assert.fail("Test should have failed");
 });
 
-it("should fail to cancel output with incorrect index", async () => {
+it("should fail to remove output with incorrect index", async () => {
   try {
-    const creatorAta = await getOrCreateAssociatedTokenAccount(
-      anchor.getProvider().connection,
-      creator,
-      creatorMint,
-      creator.publicKey,
-      true
-    );
-
-    const authAta = await getOrCreateAssociatedTokenAccount(
-      anchor.getProvider().connection,
-      creator,
-      creatorMint,
-      auth,
-      true
-    );
-
     await program.methods
-      .transmuterCancelOutputSpl(seed, new BN(1))
+      .transmuterRemoveOutput(seed, new BN(1))
       .accounts({
         creator: creator.publicKey,
         transmuter,
-        auth,
-        creatorAta: creatorAta.address,
-        authAta: authAta.address,
-        tokenProgram,
-      })
-      .signers([creator])
-      .rpc({
-        skipPreflight: true,
-      });
-  } catch (e) {
-    assert.ok(e instanceof Error);
-    return;
-  }
-  assert.fail("Test should have failed");
-});
-
-it("should fail to cancel output with incorrect index", async () => {
-  try {
-    const creatorAta = await getOrCreateAssociatedTokenAccount(
-      anchor.getProvider().connection,
-      creator,
-      creatorMint,
-      creator.publicKey,
-      true
-    );
-
-    const authAta = await getOrCreateAssociatedTokenAccount(
-      anchor.getProvider().connection,
-      creator,
-      creatorMint,
-      auth,
-      true
-    );
-
-    await program.methods
-      .transmuterCancelOutputSpl(seed, new BN(-1))
-      .accounts({
-        creator: creator.publicKey,
-        transmuter,
-        auth,
-        creatorAta: creatorAta.address,
-        authAta: authAta.address,
-        tokenProgram,
       })
       .signers([creator])
       .rpc({
@@ -351,12 +295,24 @@ it("should cancel spl output", async () => {
   );
 
   await program.methods
-    .transmuterCancelOutputSpl(seed, new BN(index))
+    .transmuterRemoveOutput(seed, new BN(index))
+    .accounts({
+      creator: creator.publicKey,
+      transmuter,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  await program.methods
+    .transmuterCancelOutputSpl(seed)
     .accounts({
       creator: creator.publicKey,
       transmuter,
       auth,
       creatorAta: creatorAta.address,
+      mint: creatorMint,
       authAta: authAta.address,
       tokenProgram,
     })
@@ -372,6 +328,12 @@ it("should cancel spl output", async () => {
   );
 
   assert.equal(transmuterStructAfter.account.outputs.length, 0);
+
+  const authAtaAfter = await getAccount(
+    anchor.getProvider().connection,
+    authAta.address
+  );
+  assert.equal(Number(authAtaAfter.amount), 0);
 });
 
 it("should resume the transmuter", async () => {
@@ -399,4 +361,243 @@ it("should resume the transmuter", async () => {
   );
 
   assert.ok(!transmuterStruct.account.locked);
-});
\ No newline at end of file
+});
+
+const timelockSeed = new BN(randomBytes(8));
+const timelockTransmuter = PublicKey.findProgramAddressSync(
+  [
+    Buffer.from("transmuter"),
+    creator.publicKey.toBytes(),
+    timelockSeed.toBuffer().reverse(),
+  ],
+  program.programId
+)[0];
+
+it("creates a timelocked transmuter", async () => {
+  const owner = await getProgramAuthority(
+    anchor.getProvider().connection,
+    programId
+  );
+
+  const transmuterConfig = {
+    input_length: 1,
+    output_length: 1,
+    recipe_timelock_seconds: 3600,
+  };
+
+  await program.methods
+    .transmuterCreate(timelockSeed, JSON.stringify(transmuterConfig))
+    .accounts({
+      creator: creator.publicKey,
+      auth: PublicKey.findProgramAddressSync(
+        [Buffer.from("auth"), timelockTransmuter.toBytes()],
+        program.programId
+      )[0],
+      transmuter: timelockTransmuter,
+      systemProgram: SystemProgram.programId,
+      owner,
+      wba: WBA,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+});
+
+it("should stage input and config edits behind the timelock", async () => {
+  const inputInfo = {
+    token_standard: "nft",
+    collection: inputCollection.nft.address.toBase58(),
+    method: "transfer",
+    amount: 1,
+  };
+
+  await program.methods
+    .transmuterSetInput(timelockSeed, JSON.stringify(inputInfo))
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: timelockTransmuter,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  await program.methods
+    .transmuterSet(timelockSeed, JSON.stringify({ input_length: 1, output_length: 1, transmute_max: 5 }))
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: timelockTransmuter,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    timelockSeed
+  );
+
+  assert.equal(transmuterStruct.account.inputs.length, 0);
+  assert.equal(transmuterStruct.account.pendingInputs.length, 1);
+  assert.equal(transmuterStruct.account.transmuteMax, null);
+  assert.equal(
+    Number(transmuterStruct.account.pendingConfig.transmuteMax),
+    5
+  );
+});
+
+it("should fail to apply the recipe before the timelock", async () => {
+  try {
+    await program.methods
+      .transmuterApplyRecipe(timelockSeed)
+      .accounts({
+        creator: creator.publicKey,
+        transmuter: timelockTransmuter,
+      })
+      .signers([creator])
+      .rpc({
+        skipPreflight: true,
+      });
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should cancel the staged recipe and config", async () => {
+  await program.methods
+    .transmuterCancelRecipe(timelockSeed)
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: timelockTransmuter,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    timelockSeed
+  );
+
+  assert.equal(transmuterStruct.account.pendingInputs.length, 0);
+  assert.equal(transmuterStruct.account.pendingConfig, null);
+  assert.equal(transmuterStruct.account.pendingEffectiveAt, null);
+});
+
+const configSeed = new BN(randomBytes(8));
+const configTransmuter = PublicKey.findProgramAddressSync(
+  [
+    Buffer.from("transmuter"),
+    creator.publicKey.toBytes(),
+    configSeed.toBuffer().reverse(),
+  ],
+  program.programId
+)[0];
+
+const CONFIG_TIMELOCK_SECONDS = 2;
+
+const applyAfterTimelock = async () => {
+  await new Promise((resolve) =>
+    setTimeout(resolve, (CONFIG_TIMELOCK_SECONDS + 2) * 1000)
+  );
+
+  await program.methods
+    .transmuterApplyRecipe(configSeed)
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: configTransmuter,
+    })
+    .signers([creator])
+    .rpc();
+};
+
+it("should apply a config only change without bumping the recipe", async () => {
+  const owner = await getProgramAuthority(
+    anchor.getProvider().connection,
+    programId
+  );
+
+  await program.methods
+    .transmuterCreate(
+      configSeed,
+      JSON.stringify({
+        input_length: 1,
+        output_length: 1,
+        recipe_timelock_seconds: CONFIG_TIMELOCK_SECONDS,
+      })
+    )
+    .accounts({
+      creator: creator.publicKey,
+      auth: PublicKey.findProgramAddressSync(
+        [Buffer.from("auth"), configTransmuter.toBytes()],
+        program.programId
+      )[0],
+      transmuter: configTransmuter,
+      systemProgram: SystemProgram.programId,
+      owner,
+      wba: WBA,
+    })
+    .signers([creator])
+    .rpc();
+
+  //The first input lands through the timelock like any recipe edit
+  await program.methods
+    .transmuterSetInput(
+      configSeed,
+      JSON.stringify({
+        token_standard: "nft",
+        collection: inputCollection.nft.address.toBase58(),
+        method: "transfer",
+        amount: 1,
+      })
+    )
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: configTransmuter,
+    })
+    .signers([creator])
+    .rpc();
+  await applyAfterTimelock();
+
+  const transmuterBefore = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    configSeed
+  );
+  assert.equal(transmuterBefore.account.inputs.length, 1);
+
+  await program.methods
+    .transmuterSet(
+      configSeed,
+      JSON.stringify({ input_length: 1, output_length: 1, transmute_max: 5 })
+    )
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: configTransmuter,
+    })
+    .signers([creator])
+    .rpc();
+  await applyAfterTimelock();
+
+  const transmuterAfter = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    configSeed
+  );
+  assert.equal(Number(transmuterAfter.account.transmuteMax), 5);
+  assert.equal(
+    transmuterAfter.account.recipeVersion.toString(),
+    transmuterBefore.account.recipeVersion.toString()
+  );
+  assert.deepEqual(
+    transmuterAfter.account.recipeHash,
+    transmuterBefore.account.recipeHash
+  );
+});
@@ -97,6 +97,7 @@ it("should fail to set one spl output to the transmuter with 0 amount", async ()
         creator: creator.publicKey,
         transmuter,
         creatorAta: creatorAta.address,
+        mint: creatorMint,
         authAta: authAta.address,
         tokenProgram,
       })
@@ -144,6 +145,7 @@ it("should set one spl output to the transmuter", async () => {
       creator: creator.publicKey,
       transmuter,
       creatorAta: creatorAta.address,
+      mint: creatorMint,
       authAta: authAta.address,
       tokenProgram,
     })
@@ -223,7 +225,7 @@ it("should handle input", async () => {
   const metadata = await getMetadata(inputMint);
 
   await program.methods
-    .userSendInput(seed, vaultSeed)
+    .userSendInput(seed, vaultSeed, null)
     .accounts({
       creator: creator.publicKey,
       user: user.publicKey,
@@ -287,6 +289,7 @@ it("should fail to claim the output if not enough funds", async () => {
             vaultAuth: vaultAuth.publicKey,
             auth,
             transmuter: transmuter.publicKey,
+            mint: creatorMint,
             authAta: authAta.address,
             userAta: userAta.address,
             tokenProgram,
@@ -349,6 +352,7 @@ it("should fail to top up too much spl", async () => {
         creator: creator.publicKey,
         transmuter,
         creatorAta: creatorAta.address,
+        mint: creatorMint,
         authAta: authAta.address,
         tokenProgram,
       })
@@ -385,6 +389,7 @@ it("should top up transmuter auth with spl", async () => {
       creator: creator.publicKey,
       transmuter,
       creatorAta: creatorAta.address,
+      mint: creatorMint,
       authAta: authAta.address,
       tokenProgram,
     })
@@ -437,6 +442,7 @@ it("should claim the output if enough funds", async () => {
           vaultAuth: vaultAuth.publicKey,
           auth,
           transmuter: transmuter.publicKey,
+          mint: creatorMint,
           authAta: authAta.address,
           userAta: userAta.address,
           tokenProgram,
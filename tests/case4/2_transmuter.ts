@@ -0,0 +1,135 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import assert from "assert";
+import { WBA, getProgramAuthority, getTransmuterStruct } from "../utils";
+import { creator, inputCollection, outputCollection } from "./1_init";
+import { program, programId } from "..";
+
+require("dotenv").config({ path: ".env" });
+
+// Random seed
+export const seed = new BN(randomBytes(8));
+
+//Vaults expire quickly so they can be refunded within the test run
+export const VAULT_TTL_SECONDS = 3;
+
+const transmuter = PublicKey.findProgramAddressSync(
+  [
+    Buffer.from("transmuter"),
+    creator.publicKey.toBytes(),
+    seed.toBuffer().reverse(),
+  ],
+  program.programId
+)[0];
+
+export const auth = PublicKey.findProgramAddressSync(
+  [Buffer.from("auth"), transmuter.toBytes()],
+  program.programId
+)[0];
+
+it("creates the transmuter", async () => {
+  const owner = await getProgramAuthority(
+    anchor.getProvider().connection,
+    programId
+  );
+
+  const transmuterConfig = {
+    input_length: 1,
+    output_length: 1,
+    vault_ttl_seconds: VAULT_TTL_SECONDS,
+  };
+
+  await program.methods
+    .transmuterCreate(seed, JSON.stringify(transmuterConfig))
+    .accounts({
+      creator: creator.publicKey,
+      auth,
+      transmuter,
+      systemProgram: SystemProgram.programId,
+      owner,
+      wba: WBA,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+
+  assert.equal(
+    Number(transmuterStruct.account.vaultTtlSeconds),
+    VAULT_TTL_SECONDS
+  );
+});
+
+it("should add one input to the transmuter", async () => {
+  const inputInfo = {
+    token_standard: "nft",
+    collection: inputCollection.nft.address.toBase58(),
+    method: "transfer",
+    amount: 1,
+  };
+
+  await program.methods
+    .transmuterSetInput(seed, JSON.stringify(inputInfo))
+    .accounts({
+      creator: creator.publicKey,
+      transmuter,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+});
+
+it("should add one output to the transmuter", async () => {
+  const outputInfo = {
+    token_standard: "nft",
+    collection: outputCollection.nft.address.toBase58(),
+    method: "mint",
+    amount: 1,
+    mint_info: {
+      title: "Refund output",
+      symbol: "NFT",
+      uri: "https://arweave.net/qF9H_BBdjf-ZIR90_z5xXsSx8WiPB3-pHA8QTlg1oeI",
+    },
+  };
+
+  await program.methods
+    .transmuterSetOutput(seed, JSON.stringify(outputInfo))
+    .accounts({
+      creator: creator.publicKey,
+      transmuter,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+});
+
+it("should resume the transmuter", async () => {
+  await program.methods
+    .transmuterResume(seed)
+    .accounts({
+      creator: creator.publicKey,
+      transmuter,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+
+  assert.ok(!transmuterStruct.account.locked);
+});
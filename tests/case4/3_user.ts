@@ -0,0 +1,372 @@
+import assert from "assert";
+import * as anchor from "@coral-xyz/anchor";
+import { creator, inputMints, user } from "./1_init";
+import {
+  TOKEN_METADATA_PROGRAM_ID,
+  confirmTx,
+  getMasterEdition,
+  getMetadata,
+  getTransmuterStruct,
+  modifyComputeUnits,
+} from "../utils";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID as associatedTokenProgram,
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID as tokenProgram,
+} from "@solana/spl-token";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { VAULT_TTL_SECONDS, auth, seed } from "./2_transmuter";
+import { randomBytes } from "crypto";
+import { program } from "..";
+
+const [vaultSeed, vaultSeed2, vaultSeed3] = [
+  new anchor.BN(randomBytes(8)),
+  new anchor.BN(randomBytes(8)),
+  new anchor.BN(randomBytes(8)),
+];
+
+//Programs only needed for core and cnft inputs
+const noAssetPrograms = {
+  coreProgram: null,
+  bubblegumProgram: null,
+  logWrapper: null,
+  compressionProgram: null,
+};
+
+const getTransmuter = () =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("transmuter"),
+      creator.publicKey.toBytes(),
+      seed.toBuffer().reverse(),
+    ],
+    program.programId
+  )[0];
+
+const getVaultAuth = (vaultSeed: anchor.BN) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("vaultAuth"),
+      getTransmuter().toBytes(),
+      user.publicKey.toBytes(),
+      vaultSeed.toBuffer().reverse(),
+    ],
+    program.programId
+  )[0];
+
+const waitForExpiry = () =>
+  new Promise((resolve) => setTimeout(resolve, (VAULT_TTL_SECONDS + 2) * 1000));
+
+const initVaultAndSendInput = async (
+  vaultSeed: anchor.BN,
+  inputMint: PublicKey
+) => {
+  const transmuter = getTransmuter();
+  const vaultAuth = getVaultAuth(vaultSeed);
+
+  await program.methods
+    .userInitVaultAuth(seed, vaultSeed)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      vaultAuth,
+      transmuter,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+
+  const ata = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    user,
+    inputMint,
+    user.publicKey,
+    true
+  );
+
+  const vault = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    user,
+    inputMint,
+    vaultAuth,
+    true
+  );
+
+  await program.methods
+    .userSendInput(seed, vaultSeed, null)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      mint: inputMint,
+      ata: ata.address,
+      metadata: await getMetadata(inputMint),
+      vaultAuth,
+      vault: vault.address,
+      tokenProgram,
+      transmuter,
+    })
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+
+  return { vaultAuth, vault: vault.address };
+};
+
+const refundVault = async (
+  vaultSeed: anchor.BN,
+  inputMint: PublicKey,
+  vault: PublicKey,
+  recipientAta: PublicKey
+) => {
+  await program.methods
+    .vaultRefund(seed, vaultSeed, [])
+    .accounts({
+      cranker: creator.publicKey,
+      creator: creator.publicKey,
+      user: user.publicKey,
+      vaultAuth: getVaultAuth(vaultSeed),
+      tokenProgram,
+      systemProgram: SystemProgram.programId,
+      ...noAssetPrograms,
+      transmuter: getTransmuter(),
+    })
+    .remainingAccounts([
+      { pubkey: inputMint, isSigner: false, isWritable: false },
+      { pubkey: vault, isSigner: false, isWritable: true },
+      { pubkey: recipientAta, isSigner: false, isWritable: true },
+    ])
+    .signers([creator])
+    .rpc()
+    .then(confirmTx);
+};
+
+let vault: PublicKey;
+
+it("should send an input to a vault", async () => {
+  ({ vault } = await initVaultAndSendInput(
+    vaultSeed,
+    inputMints[0].nft.address
+  ));
+
+  const vaultAccount = await getAccount(
+    anchor.getProvider().connection,
+    vault
+  );
+  assert.equal(Number(vaultAccount.amount), 1);
+});
+
+it("should fail to refund a vault that has not expired", async () => {
+  try {
+    const inputMint = inputMints[0].nft.address;
+    const ata = await getOrCreateAssociatedTokenAccount(
+      anchor.getProvider().connection,
+      user,
+      inputMint,
+      user.publicKey,
+      true
+    );
+
+    await refundVault(vaultSeed, inputMint, vault, ata.address);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("VaultNotExpired"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should fail to refund an unclaimed vault to the creator", async () => {
+  await waitForExpiry();
+
+  try {
+    const inputMint = inputMints[0].nft.address;
+    const creatorAta = await getOrCreateAssociatedTokenAccount(
+      anchor.getProvider().connection,
+      creator,
+      inputMint,
+      creator.publicKey,
+      true
+    );
+
+    await refundVault(vaultSeed, inputMint, vault, creatorAta.address);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidUserAta"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should refund an expired unclaimed vault to the user", async () => {
+  const inputMint = inputMints[0].nft.address;
+  const ata = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    user,
+    inputMint,
+    user.publicKey,
+    true
+  );
+
+  await refundVault(vaultSeed, inputMint, vault, ata.address);
+
+  const userAta = await getAccount(anchor.getProvider().connection, ata.address);
+  assert.equal(Number(userAta.amount), 1);
+
+  const vaultAuth = await anchor
+    .getProvider()
+    .connection.getAccountInfo(getVaultAuth(vaultSeed));
+  assert.equal(vaultAuth, null);
+});
+
+it("should refund a claimed vault the creator never resolved to the creator", async () => {
+  const inputMint = inputMints[1].nft.address;
+  const { vaultAuth, vault } = await initVaultAndSendInput(
+    vaultSeed2,
+    inputMint
+  );
+
+  const mint = await createMint(
+    anchor.getProvider().connection,
+    user,
+    auth,
+    auth,
+    0
+  );
+
+  const ata = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    user,
+    mint,
+    user.publicKey,
+    true
+  );
+
+  await program.methods
+    .userClaimOutputNft(seed, vaultSeed2, [])
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      vaultAuth,
+      auth,
+      transmuter: getTransmuter(),
+      mint,
+      ata: ata.address,
+      metadata: await getMetadata(mint),
+      masterEdition: await getMasterEdition(mint),
+      tokenProgram,
+      associatedTokenProgram,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      sysvarInstructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+    })
+    .preInstructions([modifyComputeUnits])
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+
+  await waitForExpiry();
+
+  const creatorAta = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    creator,
+    inputMint,
+    creator.publicKey,
+    true
+  );
+
+  await refundVault(vaultSeed2, inputMint, vault, creatorAta.address);
+
+  const creatorAtaAfter = await getAccount(
+    anchor.getProvider().connection,
+    creatorAta.address
+  );
+  assert.equal(Number(creatorAtaAfter.amount), 1);
+
+  const vaultAuthAfter = await anchor
+    .getProvider()
+    .connection.getAccountInfo(vaultAuth);
+  assert.equal(vaultAuthAfter, null);
+});
+
+it("should fail for the creator to abort the user vault", async () => {
+  const inputMint = inputMints[2].nft.address;
+  ({ vault } = await initVaultAndSendInput(vaultSeed3, inputMint));
+
+  try {
+    const ata = await getOrCreateAssociatedTokenAccount(
+      anchor.getProvider().connection,
+      user,
+      inputMint,
+      user.publicKey,
+      true
+    );
+
+    await program.methods
+      .userAbortVault(seed, vaultSeed3, [])
+      .accounts({
+        creator: creator.publicKey,
+        user: creator.publicKey,
+        vaultAuth: getVaultAuth(vaultSeed3),
+        tokenProgram,
+        systemProgram: SystemProgram.programId,
+        ...noAssetPrograms,
+        transmuter: getTransmuter(),
+      })
+      .remainingAccounts([
+        { pubkey: inputMint, isSigner: false, isWritable: false },
+        { pubkey: vault, isSigner: false, isWritable: true },
+        { pubkey: ata.address, isSigner: false, isWritable: true },
+      ])
+      .signers([creator])
+      .rpc();
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should abort the vault and get the input back", async () => {
+  const inputMint = inputMints[2].nft.address;
+  const ata = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    user,
+    inputMint,
+    user.publicKey,
+    true
+  );
+
+  await program.methods
+    .userAbortVault(seed, vaultSeed3, [])
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      vaultAuth: getVaultAuth(vaultSeed3),
+      tokenProgram,
+      systemProgram: SystemProgram.programId,
+      ...noAssetPrograms,
+      transmuter: getTransmuter(),
+    })
+    .remainingAccounts([
+      { pubkey: inputMint, isSigner: false, isWritable: false },
+      { pubkey: vault, isSigner: false, isWritable: true },
+      { pubkey: ata.address, isSigner: false, isWritable: true },
+    ])
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+
+  const userAta = await getAccount(anchor.getProvider().connection, ata.address);
+  assert.equal(Number(userAta.amount), 1);
+
+  const transmuter = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+  assert.equal(Number(transmuter.account.openVaults), 0);
+});
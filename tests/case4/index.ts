@@ -0,0 +1,8 @@
+import { importTest } from "../utils/helpers";
+
+// expired vault refunds and aborts
+describe("Transmuter case 4", () => {
+  importTest("Set up tests", `${__dirname}/1_init`);
+  importTest("Transmuter tests", `${__dirname}/2_transmuter`);
+  importTest("User tests", `${__dirname}/3_user`);
+});
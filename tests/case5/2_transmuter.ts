@@ -0,0 +1,193 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import assert from "assert";
+import { WBA, getProgramAuthority, getTransmuterStruct } from "../utils";
+import { creator, inputCollection, outputCollection } from "./1_init";
+import { program, programId } from "..";
+
+require("dotenv").config({ path: ".env" });
+
+// Random seeds, the other transmuters take inputs or outputs a batch can't handle
+export const seed = new BN(randomBytes(8));
+export const coreSeed = new BN(randomBytes(8));
+export const solSeed = new BN(randomBytes(8));
+
+export const getTransmuter = (seed: BN) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("transmuter"),
+      creator.publicKey.toBytes(),
+      seed.toBuffer().reverse(),
+    ],
+    program.programId
+  )[0];
+
+export const getAuth = (seed: BN) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("auth"), getTransmuter(seed).toBytes()],
+    program.programId
+  )[0];
+
+const setNftOutput = async (seed: BN) => {
+  const outputInfo = {
+    token_standard: "nft",
+    collection: outputCollection.nft.address.toBase58(),
+    method: "mint",
+    amount: 1,
+    mint_info: {
+      title: "Batch output",
+      symbol: "NFT",
+      uri: "https://arweave.net/qF9H_BBdjf-ZIR90_z5xXsSx8WiPB3-pHA8QTlg1oeI",
+    },
+  };
+
+  await program.methods
+    .transmuterSetOutput(seed, JSON.stringify(outputInfo))
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(seed),
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+};
+
+const nftInputInfo = () => ({
+  token_standard: "nft",
+  collection: inputCollection.nft.address.toBase58(),
+  method: "burn",
+  amount: 1,
+});
+
+const createTransmuter = async (
+  seed: BN,
+  inputInfo: object,
+  setOutput: (seed: BN) => Promise<void> = setNftOutput,
+  outputLength = 1
+) => {
+  const owner = await getProgramAuthority(
+    anchor.getProvider().connection,
+    programId
+  );
+
+  const transmuterConfig = {
+    input_length: 1,
+    output_length: outputLength,
+  };
+
+  await program.methods
+    .transmuterCreate(seed, JSON.stringify(transmuterConfig))
+    .accounts({
+      creator: creator.publicKey,
+      auth: getAuth(seed),
+      transmuter: getTransmuter(seed),
+      systemProgram: SystemProgram.programId,
+      owner,
+      wba: WBA,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  await program.methods
+    .transmuterSetInput(seed, JSON.stringify(inputInfo))
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(seed),
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  await setOutput(seed);
+
+  await program.methods
+    .transmuterResume(seed)
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(seed),
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+};
+
+it("creates a transmuter burning an nft input", async () => {
+  await createTransmuter(seed, nftInputInfo());
+
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+
+  assert.equal(transmuterStruct.account.inputs.length, 1);
+  assert.equal(transmuterStruct.account.outputs.length, 1);
+  assert.ok(!transmuterStruct.account.locked);
+});
+
+it("creates a transmuter taking a core input", async () => {
+  await createTransmuter(coreSeed, {
+    token_standard: "core",
+    collection: inputCollection.nft.address.toBase58(),
+    method: "transfer",
+    amount: 1,
+  });
+});
+
+//The sol output comes first, so it can be claimed on its own before batching the nft
+it("creates a transmuter with a sol and an nft output", async () => {
+  const setOutputs = async (seed: BN) => {
+    const escrowSeed = new BN(randomBytes(8));
+    const escrow = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("solEscrow"),
+        getTransmuter(seed).toBytes(),
+        escrowSeed.toBuffer().reverse(),
+      ],
+      program.programId
+    )[0];
+
+    const outputInfo = {
+      token_standard: "sol",
+      method: "transfer",
+      amount: 1000000,
+      escrow: escrow.toBase58(),
+    };
+
+    await program.methods
+      .transmuterSetOutputSol(
+        seed,
+        escrowSeed,
+        JSON.stringify(outputInfo),
+        new BN(1000000)
+      )
+      .accounts({
+        creator: creator.publicKey,
+        transmuter: getTransmuter(seed),
+        escrow,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc({
+        skipPreflight: true,
+      });
+
+    await setNftOutput(seed);
+  };
+
+  await createTransmuter(solSeed, nftInputInfo(), setOutputs, 2);
+
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    solSeed
+  );
+  assert.equal(transmuterStruct.account.outputs.length, 2);
+});
@@ -0,0 +1,378 @@
+import assert from "assert";
+import * as anchor from "@coral-xyz/anchor";
+import { creator, inputMints, user } from "./1_init";
+import {
+  TOKEN_METADATA_PROGRAM_ID,
+  confirmTx,
+  getMasterEdition,
+  getMetadata,
+  getTransmuterStruct,
+  modifyComputeUnits,
+} from "../utils";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID as associatedTokenProgram,
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID as tokenProgram,
+} from "@solana/spl-token";
+import { AccountMeta, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  coreSeed,
+  getAuth,
+  getTransmuter,
+  seed,
+  solSeed,
+} from "./2_transmuter";
+import { randomBytes } from "crypto";
+import { program } from "..";
+
+const toMeta = (pubkey: PublicKey, isWritable = true): AccountMeta => ({
+  pubkey,
+  isSigner: false,
+  isWritable,
+});
+
+export const getVaultAuth = (seed: anchor.BN, vaultSeed: anchor.BN) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("vaultAuth"),
+      getTransmuter(seed).toBytes(),
+      user.publicKey.toBytes(),
+      vaultSeed.toBuffer().reverse(),
+    ],
+    program.programId
+  )[0];
+
+const initVaultAndSendInput = async (
+  seed: anchor.BN,
+  vaultSeed: anchor.BN,
+  inputMint: PublicKey
+) => {
+  const vaultAuth = getVaultAuth(seed, vaultSeed);
+
+  await program.methods
+    .userInitVaultAuth(seed, vaultSeed)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      vaultAuth,
+      transmuter: getTransmuter(seed),
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+
+  const ata = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    user,
+    inputMint,
+    user.publicKey,
+    true
+  );
+
+  const vault = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    user,
+    inputMint,
+    vaultAuth,
+    true
+  );
+
+  await program.methods
+    .userSendInput(seed, vaultSeed, null)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      mint: inputMint,
+      ata: ata.address,
+      metadata: await getMetadata(inputMint),
+      vaultAuth,
+      vault: vault.address,
+      tokenProgram,
+      transmuter: getTransmuter(seed),
+    })
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+
+  return vaultAuth;
+};
+
+const claimOutputs = (
+  seed: anchor.BN,
+  vaultSeed: anchor.BN,
+  indexesJson: string,
+  remainingAccounts: AccountMeta[]
+) =>
+  program.methods
+    .userClaimOutputs(seed, vaultSeed, indexesJson, [])
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      transmuter: getTransmuter(seed),
+      auth: getAuth(seed),
+      vaultAuth: getVaultAuth(seed, vaultSeed),
+      tokenProgram,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      associatedTokenProgram,
+      systemProgram: SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      sysvarInstructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+    })
+    .remainingAccounts(remainingAccounts)
+    .preInstructions([modifyComputeUnits])
+    .signers([user])
+    .rpc();
+
+//Remaining accounts for one nft input and one minted nft output
+const getTransmuteAccounts = async (seed: anchor.BN, inputMint: PublicKey) => {
+  const inputAta = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    user,
+    inputMint,
+    user.publicKey,
+    true
+  );
+
+  const outputMint = await createMint(
+    anchor.getProvider().connection,
+    user,
+    getAuth(seed),
+    getAuth(seed),
+    0
+  );
+  const outputAta = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    user,
+    outputMint,
+    user.publicKey,
+    true
+  );
+
+  const remainingAccounts = [
+    toMeta(inputMint),
+    toMeta(inputAta.address),
+    toMeta(await getMetadata(inputMint)),
+    toMeta(outputMint),
+    toMeta(outputAta.address),
+    toMeta(await getMetadata(outputMint)),
+    toMeta(await getMasterEdition(outputMint)),
+  ];
+  const inputIndexes = [{ mint: 0, ata: 1, metadata: 2 }];
+  const outputIndexes = [{ mint: 3, ata: 4, metadata: 5, master_edition: 6 }];
+
+  return {
+    inputAta: inputAta.address,
+    outputAta: outputAta.address,
+    remainingAccounts,
+    inputIndexes,
+    outputIndexes,
+  };
+};
+
+const transmute = (
+  seed: anchor.BN,
+  inputIndexesJson: string,
+  outputIndexesJson: string,
+  remainingAccounts: AccountMeta[]
+) =>
+  program.methods
+    .userTransmute(seed, inputIndexesJson, outputIndexesJson)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      transmuter: getTransmuter(seed),
+      auth: getAuth(seed),
+      tokenProgram,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      associatedTokenProgram,
+      systemProgram: SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      sysvarInstructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+    })
+    .remainingAccounts(remainingAccounts)
+    .preInstructions([modifyComputeUnits])
+    .signers([user])
+    .rpc();
+
+it("should fail to transmute with malformed indexes", async () => {
+  try {
+    const { remainingAccounts, outputIndexes } = await getTransmuteAccounts(
+      seed,
+      inputMints[0].nft.address
+    );
+
+    await transmute(
+      seed,
+      "[{ not json",
+      JSON.stringify(outputIndexes),
+      remainingAccounts
+    );
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("JSONParseError"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should fail to transmute a recipe with a core input", async () => {
+  try {
+    const { remainingAccounts, inputIndexes, outputIndexes } =
+      await getTransmuteAccounts(coreSeed, inputMints[0].nft.address);
+
+    await transmute(
+      coreSeed,
+      JSON.stringify(inputIndexes),
+      JSON.stringify(outputIndexes),
+      remainingAccounts
+    );
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("UnsupportedInput"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should transmute an nft input into an nft output", async () => {
+  const inputMint = inputMints[0].nft.address;
+  const { inputAta, outputAta, remainingAccounts, inputIndexes, outputIndexes } =
+    await getTransmuteAccounts(seed, inputMint);
+
+  await transmute(
+    seed,
+    JSON.stringify(inputIndexes),
+    JSON.stringify(outputIndexes),
+    remainingAccounts
+  ).then(confirmTx);
+
+  //The burnt input leaves an empty ata behind
+  const inputAtaAfter = await getAccount(
+    anchor.getProvider().connection,
+    inputAta
+  );
+  assert.equal(Number(inputAtaAfter.amount), 0);
+
+  const outputAtaAfter = await getAccount(
+    anchor.getProvider().connection,
+    outputAta
+  );
+  assert.equal(Number(outputAtaAfter.amount), 1);
+
+  const transmuter = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+  assert.equal(transmuter.account.transmuteCount, 1);
+});
+
+export const batchVaultSeed = new anchor.BN(randomBytes(8));
+export const solVaultSeed = new anchor.BN(randomBytes(8));
+
+it("should fail to batch a sol output with an nft output", async () => {
+  await initVaultAndSendInput(
+    solSeed,
+    solVaultSeed,
+    inputMints[2].nft.address
+  );
+
+  try {
+    const userAta = await getOrCreateAssociatedTokenAccount(
+      anchor.getProvider().connection,
+      user,
+      inputMints[2].nft.address,
+      user.publicKey,
+      true
+    );
+
+    const outputMint = await createMint(
+      anchor.getProvider().connection,
+      user,
+      getAuth(solSeed),
+      getAuth(solSeed),
+      0
+    );
+    const outputAta = await getOrCreateAssociatedTokenAccount(
+      anchor.getProvider().connection,
+      user,
+      outputMint,
+      user.publicKey,
+      true
+    );
+
+    await claimOutputs(
+      solSeed,
+      solVaultSeed,
+      JSON.stringify([
+        { mint: 0, ata: 1 },
+        { mint: 2, ata: 3, metadata: 4, master_edition: 5 },
+      ]),
+      [
+        toMeta(inputMints[2].nft.address),
+        toMeta(userAta.address),
+        toMeta(outputMint),
+        toMeta(outputAta.address),
+        toMeta(await getMetadata(outputMint)),
+        toMeta(await getMasterEdition(outputMint)),
+      ]
+    );
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("UnsupportedOutput"));
+
+    //Nothing in the batch was claimed
+    const vaultAuth = await program.account.vaultAuth.fetch(
+      getVaultAuth(solSeed, solVaultSeed)
+    );
+    assert.ok(vaultAuth.handledOutputs.every((output) => output === null));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should claim every output of a vault in one instruction", async () => {
+  await initVaultAndSendInput(seed, batchVaultSeed, inputMints[1].nft.address);
+
+  const outputMint = await createMint(
+    anchor.getProvider().connection,
+    user,
+    getAuth(seed),
+    getAuth(seed),
+    0
+  );
+  const outputAta = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    user,
+    outputMint,
+    user.publicKey,
+    true
+  );
+
+  await claimOutputs(
+    seed,
+    batchVaultSeed,
+    JSON.stringify([{ mint: 0, ata: 1, metadata: 2, master_edition: 3 }]),
+    [
+      toMeta(outputMint),
+      toMeta(outputAta.address),
+      toMeta(await getMetadata(outputMint)),
+      toMeta(await getMasterEdition(outputMint)),
+    ]
+  ).then(confirmTx);
+
+  const outputAtaAfter = await getAccount(
+    anchor.getProvider().connection,
+    outputAta.address
+  );
+  assert.equal(Number(outputAtaAfter.amount), 1);
+
+  const vaultAuth = await program.account.vaultAuth.fetch(
+    getVaultAuth(seed, batchVaultSeed)
+  );
+  assert.ok(vaultAuth.handledOutputs.every((output) => output !== null));
+  assert.ok(!vaultAuth.creatorLocked);
+});
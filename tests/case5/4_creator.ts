@@ -0,0 +1,148 @@
+import assert from "assert";
+import * as anchor from "@coral-xyz/anchor";
+import { creator, inputMints, user } from "./1_init";
+import { confirmTx, getTransmuterStruct } from "../utils";
+import {
+  getOrCreateAssociatedTokenAccount,
+  TOKEN_PROGRAM_ID as tokenProgram,
+} from "@solana/spl-token";
+import { AccountMeta, Keypair, PublicKey } from "@solana/web3.js";
+import { getTransmuter, seed, solSeed } from "./2_transmuter";
+import { batchVaultSeed, getVaultAuth, solVaultSeed } from "./3_user";
+import { program } from "..";
+
+//One (vault_auth, user, mint, vault, creator_ata) group per input
+const getResolveAccounts = async (
+  seed: anchor.BN,
+  vaultSeed: anchor.BN,
+  inputMint: PublicKey
+): Promise<AccountMeta[]> => {
+  const vaultAuth = getVaultAuth(seed, vaultSeed);
+
+  const vault = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    creator,
+    inputMint,
+    vaultAuth,
+    true
+  );
+
+  const creatorAta = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    creator,
+    inputMint,
+    creator.publicKey,
+    true
+  );
+
+  return [vaultAuth, user.publicKey, inputMint, vault.address, creatorAta.address].map(
+    (pubkey) => ({ pubkey, isSigner: false, isWritable: true })
+  );
+};
+
+const resolveInputs = (
+  seed: anchor.BN,
+  cranker: Keypair,
+  remainingAccounts: AccountMeta[]
+) =>
+  program.methods
+    .creatorResolveInputs(seed)
+    .accounts({
+      cranker: cranker.publicKey,
+      creator: creator.publicKey,
+      tokenProgram,
+      transmuter: getTransmuter(seed),
+    })
+    .remainingAccounts(remainingAccounts)
+    .signers([cranker])
+    .rpc();
+
+it("should fail to batch resolve a vault that was not claimed", async () => {
+  try {
+    const remainingAccounts = await getResolveAccounts(
+      solSeed,
+      solVaultSeed,
+      inputMints[2].nft.address
+    );
+
+    await resolveInputs(solSeed, creator, remainingAccounts);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("NotClaimed"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should restrict resolution to the creator", async () => {
+  const transmuterConfig = {
+    input_length: 1,
+    output_length: 1,
+    creator_resolve_only: true,
+  };
+
+  await program.methods
+    .transmuterSet(seed, JSON.stringify(transmuterConfig))
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(seed),
+    })
+    .signers([creator])
+    .rpc()
+    .then(confirmTx);
+
+  const transmuter = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+  assert.ok(transmuter.account.creatorResolveOnly);
+});
+
+it("should fail for a user to batch resolve a creator only transmuter", async () => {
+  try {
+    const remainingAccounts = await getResolveAccounts(
+      seed,
+      batchVaultSeed,
+      inputMints[1].nft.address
+    );
+
+    await resolveInputs(seed, user, remainingAccounts);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("CreatorResolveOnly"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should batch resolve the claimed vault", async () => {
+  const transmuterBefore = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+
+  const remainingAccounts = await getResolveAccounts(
+    seed,
+    batchVaultSeed,
+    inputMints[1].nft.address
+  );
+
+  await resolveInputs(seed, creator, remainingAccounts).then(confirmTx);
+
+  const vaultAuth = await anchor
+    .getProvider()
+    .connection.getAccountInfo(getVaultAuth(seed, batchVaultSeed));
+  assert.equal(vaultAuth, null);
+
+  const transmuterAfter = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+  assert.equal(
+    Number(transmuterAfter.account.openVaults),
+    Number(transmuterBefore.account.openVaults) - 1
+  );
+});
@@ -0,0 +1,9 @@
+import { importTest } from "../utils/helpers";
+
+// batched inputs and outputs
+describe("Transmuter case 5", () => {
+  importTest("Set up tests", `${__dirname}/1_init`);
+  importTest("Transmuter tests", `${__dirname}/2_transmuter`);
+  importTest("User tests", `${__dirname}/3_user`);
+  importTest("Creator tests", `${__dirname}/4_creator`);
+});
@@ -0,0 +1,70 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import assert from "assert";
+import {
+  confirmTxs,
+  createCoreAsset,
+  createCoreCollection,
+  getCoreOwner,
+} from "../utils";
+
+require("dotenv").config({ path: ".env" });
+
+// Set up our keys
+export const [creator, user] = [new Keypair(), new Keypair()];
+console.log(`creator: ${creator.publicKey}`);
+console.log(`user: ${user.publicKey}`);
+
+const uri = "https://arweave.net/qF9H_BBdjf-ZIR90_z5xXsSx8WiPB3-pHA8QTlg1oeI";
+
+export let inputCollection: PublicKey;
+export let inputAssets: PublicKey[] = [];
+export let strayAsset: PublicKey;
+
+it("Airdrop", async () => {
+  await Promise.all(
+    [creator, user].map(async (key) => {
+      return await anchor
+        .getProvider()
+        .connection.requestAirdrop(
+          key.publicKey,
+          100 * anchor.web3.LAMPORTS_PER_SOL
+        );
+    })
+  ).then(confirmTxs);
+});
+
+it("Creates the core collection", async () => {
+  inputCollection = await createCoreCollection(
+    creator,
+    "Input collection",
+    uri
+  );
+  console.log(`input collection: ${inputCollection}`);
+});
+
+it("mints input core assets", async () => {
+  for (let i = 0; i < 2; i++) {
+    const asset = await createCoreAsset(
+      creator,
+      user.publicKey,
+      inputCollection,
+      `Core input #${i + 1}`,
+      uri
+    );
+    inputAssets.push(asset);
+
+    console.log(`The asset #${i + 1}: ${asset}`);
+  }
+
+  //Not part of the input collection
+  strayAsset = await createCoreAsset(
+    user,
+    user.publicKey,
+    null,
+    "Stray asset",
+    uri
+  );
+
+  assert.ok((await getCoreOwner(inputAssets[0])).equals(user.publicKey));
+});
@@ -0,0 +1,118 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import assert from "assert";
+import { WBA, getProgramAuthority, getTransmuterStruct } from "../utils";
+import { creator, inputCollection } from "./1_init";
+import { program, programId } from "..";
+
+require("dotenv").config({ path: ".env" });
+
+// Random seed
+export const seed = new BN(randomBytes(8));
+
+export const transmuter = PublicKey.findProgramAddressSync(
+  [
+    Buffer.from("transmuter"),
+    creator.publicKey.toBytes(),
+    seed.toBuffer().reverse(),
+  ],
+  program.programId
+)[0];
+
+export const auth = PublicKey.findProgramAddressSync(
+  [Buffer.from("auth"), transmuter.toBytes()],
+  program.programId
+)[0];
+
+it("creates the transmuter", async () => {
+  const owner = await getProgramAuthority(
+    anchor.getProvider().connection,
+    programId
+  );
+
+  const transmuterConfig = {
+    input_length: 1,
+    output_length: 1,
+  };
+
+  await program.methods
+    .transmuterCreate(seed, JSON.stringify(transmuterConfig))
+    .accounts({
+      creator: creator.publicKey,
+      auth,
+      transmuter,
+      systemProgram: SystemProgram.programId,
+      owner,
+      wba: WBA,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+});
+
+it("should set one core input and one core output", async () => {
+  const inputInfo = {
+    token_standard: "core",
+    collection: inputCollection.toBase58(),
+    method: "transfer",
+    amount: 1,
+  };
+
+  await program.methods
+    .transmuterSetInput(seed, JSON.stringify(inputInfo))
+    .accounts({
+      creator: creator.publicKey,
+      transmuter,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  //No collection, the asset update authority is the creator
+  const outputInfo = {
+    token_standard: "core",
+    method: "mint",
+    amount: 1,
+    mint_info: {
+      title: "Core output",
+      symbol: "CORE",
+      uri: "https://arweave.net/qF9H_BBdjf-ZIR90_z5xXsSx8WiPB3-pHA8QTlg1oeI",
+    },
+  };
+
+  await program.methods
+    .transmuterSetOutput(seed, JSON.stringify(outputInfo))
+    .accounts({
+      creator: creator.publicKey,
+      transmuter,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  await program.methods
+    .transmuterResume(seed)
+    .accounts({
+      creator: creator.publicKey,
+      transmuter,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+
+  assert.deepEqual(JSON.parse(transmuterStruct.account.inputs[0]), inputInfo);
+  assert.equal(transmuterStruct.account.outputs.length, 1);
+  assert.ok(!transmuterStruct.account.locked);
+});
@@ -0,0 +1,164 @@
+import assert from "assert";
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import {
+  creator,
+  inputAssets,
+  inputCollection,
+  strayAsset,
+  user,
+} from "./1_init";
+import { auth, seed, transmuter } from "./2_transmuter";
+import {
+  MPL_CORE_PROGRAM_ID,
+  confirmTx,
+  getCoreOwner,
+  getvaultAuthStruct,
+} from "../utils";
+import { program } from "..";
+
+export const vaultSeed = new anchor.BN(randomBytes(8));
+
+export const vaultAuth = PublicKey.findProgramAddressSync(
+  [
+    Buffer.from("vaultAuth"),
+    transmuter.toBytes(),
+    user.publicKey.toBytes(),
+    vaultSeed.toBuffer().reverse(),
+  ],
+  program.programId
+)[0];
+
+export const outputAsset = Keypair.generate();
+
+const sendInputCore = (
+  asset: PublicKey,
+  collection: PublicKey | null,
+  slotIndex: anchor.BN | null = null
+) =>
+  program.methods
+    .userSendInputCore(seed, vaultSeed, slotIndex)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      asset,
+      collection,
+      transmuter,
+      vaultAuth,
+      coreProgram: MPL_CORE_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([user])
+    .rpc();
+
+const claimOutputCore = (collection: PublicKey | null) =>
+  program.methods
+    .userClaimOutputCore(seed, vaultSeed, [])
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      transmuter,
+      auth,
+      vaultAuth,
+      asset: outputAsset.publicKey,
+      collection,
+      coreProgram: MPL_CORE_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([user, outputAsset])
+    .rpc();
+
+it("should init the vault auth", async () => {
+  await program.methods
+    .userInitVaultAuth(seed, vaultSeed)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      vaultAuth,
+      transmuter,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+});
+
+it("should fail to send a core asset outside the input collection", async () => {
+  try {
+    await sendInputCore(strayAsset, null);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidInputAccount"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should fail to send a core asset to a slot out of range", async () => {
+  try {
+    await sendInputCore(inputAssets[0], inputCollection, new anchor.BN(1));
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidIndex"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should send a core asset into the requested slot", async () => {
+  await sendInputCore(inputAssets[0], inputCollection, new anchor.BN(0)).then(
+    confirmTx
+  );
+
+  assert.ok((await getCoreOwner(inputAssets[0])).equals(vaultAuth));
+
+  const vaultAuthStruct = await getvaultAuthStruct(
+    program,
+    transmuter,
+    user.publicKey,
+    vaultSeed
+  );
+  assert.ok(vaultAuthStruct.account.handledInputs[0].equals(inputAssets[0]));
+});
+
+it("should fail to send a core asset already in the vault", async () => {
+  try {
+    await sendInputCore(inputAssets[0], inputCollection);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("DuplicateInputAccount"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should fail to claim a core output into the wrong collection", async () => {
+  try {
+    await claimOutputCore(inputCollection);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidCollection"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should claim a core asset out", async () => {
+  await claimOutputCore(null).then(confirmTx);
+
+  assert.ok(
+    (await getCoreOwner(outputAsset.publicKey)).equals(user.publicKey)
+  );
+
+  const vaultAuthStruct = await getvaultAuthStruct(
+    program,
+    transmuter,
+    user.publicKey,
+    vaultSeed
+  );
+  assert.ok(
+    vaultAuthStruct.account.handledOutputs[0].equals(outputAsset.publicKey)
+  );
+  assert.ok(!vaultAuthStruct.account.creatorLocked);
+});
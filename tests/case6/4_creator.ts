@@ -0,0 +1,67 @@
+import assert from "assert";
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { creator, inputAssets, inputCollection, user } from "./1_init";
+import { seed, transmuter } from "./2_transmuter";
+import { vaultAuth, vaultSeed } from "./3_user";
+import {
+  MPL_CORE_PROGRAM_ID,
+  RESOLVE_TIP_LAMPORTS,
+  confirmTx,
+  getCoreOwner,
+} from "../utils";
+import { program } from "..";
+
+const resolveInputCore = (asset: PublicKey, cranker: Keypair = creator) =>
+  program.methods
+    .creatorResolveInputCore(seed, vaultSeed)
+    .accounts({
+      cranker: cranker.publicKey,
+      creator: creator.publicKey,
+      user: user.publicKey,
+      asset,
+      collection: inputCollection,
+      transmuter,
+      vaultAuth,
+      coreProgram: MPL_CORE_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([cranker])
+    .rpc();
+
+it("should fail to resolve a core asset that was not sent", async () => {
+  try {
+    await resolveInputCore(inputAssets[1]);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidInputAccount"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should resolve the core input to the creator and tip the cranker", async () => {
+  const cranker = new Keypair();
+  await anchor
+    .getProvider()
+    .connection.requestAirdrop(cranker.publicKey, anchor.web3.LAMPORTS_PER_SOL)
+    .then(confirmTx);
+
+  const balanceBefore = await anchor
+    .getProvider()
+    .connection.getBalance(cranker.publicKey);
+
+  await resolveInputCore(inputAssets[0], cranker).then(confirmTx);
+
+  const balanceAfter = await anchor
+    .getProvider()
+    .connection.getBalance(cranker.publicKey);
+  assert.equal(balanceAfter - balanceBefore, RESOLVE_TIP_LAMPORTS);
+
+  assert.ok((await getCoreOwner(inputAssets[0])).equals(creator.publicKey));
+
+  const vaultAuthAccount = await anchor
+    .getProvider()
+    .connection.getAccountInfo(vaultAuth);
+  assert.equal(vaultAuthAccount, null);
+});
@@ -0,0 +1,85 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  CreateNftOutput,
+  Metaplex,
+  keypairIdentity,
+  mockStorage,
+} from "@metaplex-foundation/js";
+import {
+  MetadataArgs,
+  TokenProgramVersion,
+  TokenStandard,
+} from "@metaplex-foundation/mpl-bubblegum";
+import { confirmTxs, createTree, mintCnftToCollection } from "../utils";
+
+require("dotenv").config({ path: ".env" });
+
+// Set up our keys
+export const [creator, user] = [new Keypair(), new Keypair()];
+console.log(`creator: ${creator.publicKey}`);
+console.log(`user: ${user.publicKey}`);
+
+export const creatorMetaplex = Metaplex.make(anchor.getProvider().connection)
+  .use(keypairIdentity(creator))
+  .use(mockStorage());
+
+export let inputCollection: CreateNftOutput;
+export let inputTree: PublicKey;
+export let inputMetadata: MetadataArgs;
+
+it("Airdrop", async () => {
+  await Promise.all(
+    [creator, user].map(async (key) => {
+      return await anchor
+        .getProvider()
+        .connection.requestAirdrop(
+          key.publicKey,
+          100 * anchor.web3.LAMPORTS_PER_SOL
+        );
+    })
+  ).then(confirmTxs);
+});
+
+it("Creates the input collection", async () => {
+  inputCollection = await creatorMetaplex.nfts().create({
+    name: "Input collection",
+    symbol: "INPT",
+    sellerFeeBasisPoints: 500,
+    uri: "https://arweave.net/qF9H_BBdjf-ZIR90_z5xXsSx8WiPB3-pHA8QTlg1oeI",
+    isCollection: true,
+  });
+});
+
+it("mints the input cNFT into the creator tree", async () => {
+  inputTree = await createTree(creator);
+
+  inputMetadata = {
+    name: "Compressed input",
+    symbol: "CNFT",
+    uri: "https://arweave.net/qF9H_BBdjf-ZIR90_z5xXsSx8WiPB3-pHA8QTlg1oeI",
+    sellerFeeBasisPoints: 500,
+    primarySaleHappened: false,
+    isMutable: true,
+    editionNonce: null,
+    tokenStandard: TokenStandard.NonFungible,
+    collection: { verified: true, key: inputCollection.mintAddress },
+    uses: null,
+    tokenProgramVersion: TokenProgramVersion.Original,
+    creators: [{ address: creator.publicKey, verified: false, share: 100 }],
+  };
+
+  await mintCnftToCollection(
+    creator,
+    user.publicKey,
+    inputTree,
+    {
+      mint: inputCollection.mintAddress,
+      metadata: inputCollection.metadataAddress,
+      edition: inputCollection.masterEditionAddress,
+    },
+    inputMetadata
+  );
+
+  console.log(`input tree: ${inputTree}`);
+});
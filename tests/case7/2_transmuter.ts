@@ -0,0 +1,182 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import assert from "assert";
+import { CreateNftOutput } from "@metaplex-foundation/js";
+import {
+  WBA,
+  createTree,
+  getProgramAuthority,
+  getTransmuterStruct,
+} from "../utils";
+import { creator, creatorMetaplex, inputCollection } from "./1_init";
+import { program, programId } from "..";
+
+require("dotenv").config({ path: ".env" });
+
+// Random seeds, the cnft transmuter mints its output into a tree
+export const seed = new BN(randomBytes(8));
+export const cnftSeed = new BN(randomBytes(8));
+
+export let outputTree: PublicKey;
+export let cnftCollection: CreateNftOutput;
+
+export const getTransmuter = (seed: BN) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("transmuter"),
+      creator.publicKey.toBytes(),
+      seed.toBuffer().reverse(),
+    ],
+    program.programId
+  )[0];
+
+export const getAuth = (seed: BN) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("auth"), getTransmuter(seed).toBytes()],
+    program.programId
+  )[0];
+
+export const cnftInputInfo = () => ({
+  token_standard: "cnft",
+  collection: inputCollection.mintAddress.toBase58(),
+  method: "transfer",
+  amount: 1,
+});
+
+export const createTransmuter = async (seed: BN, outputInfo: object) => {
+  const owner = await getProgramAuthority(
+    anchor.getProvider().connection,
+    programId
+  );
+
+  const transmuterConfig = {
+    input_length: 1,
+    output_length: 1,
+  };
+
+  await program.methods
+    .transmuterCreate(seed, JSON.stringify(transmuterConfig))
+    .accounts({
+      creator: creator.publicKey,
+      auth: getAuth(seed),
+      transmuter: getTransmuter(seed),
+      systemProgram: SystemProgram.programId,
+      owner,
+      wba: WBA,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  await program.methods
+    .transmuterSetInput(seed, JSON.stringify(cnftInputInfo()))
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(seed),
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  await program.methods
+    .transmuterSetOutput(seed, JSON.stringify(outputInfo))
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(seed),
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  await program.methods
+    .transmuterResume(seed)
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(seed),
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+};
+
+it("creates a transmuter taking a cnft input", async () => {
+  const outputCollection = await creatorMetaplex.nfts().create({
+    name: "Output collection",
+    symbol: "OUPT",
+    sellerFeeBasisPoints: 500,
+    uri: "https://arweave.net/qF9H_BBdjf-ZIR90_z5xXsSx8WiPB3-pHA8QTlg1oeI",
+    isCollection: true,
+  });
+
+  await createTransmuter(seed, {
+    token_standard: "nft",
+    collection: outputCollection.mintAddress.toBase58(),
+    method: "mint",
+    amount: 1,
+    mint_info: {
+      title: "Output",
+      symbol: "NFT",
+      uri: "https://arweave.net/qF9H_BBdjf-ZIR90_z5xXsSx8WiPB3-pHA8QTlg1oeI",
+    },
+  });
+
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+
+  assert.deepEqual(
+    JSON.parse(transmuterStruct.account.inputs[0]),
+    cnftInputInfo()
+  );
+  assert.ok(!transmuterStruct.account.locked);
+});
+
+it("creates a transmuter minting cnft outputs", async () => {
+  //The auth PDA mints into the tree and verifies the collection
+  outputTree = await createTree(creator, getAuth(cnftSeed));
+
+  cnftCollection = await creatorMetaplex.nfts().create({
+    name: "Compressed output collection",
+    symbol: "COUT",
+    sellerFeeBasisPoints: 500,
+    uri: "https://arweave.net/qF9H_BBdjf-ZIR90_z5xXsSx8WiPB3-pHA8QTlg1oeI",
+    isCollection: true,
+  });
+
+  await creatorMetaplex.nfts().update({
+    nftOrSft: cnftCollection.nft,
+    newUpdateAuthority: getAuth(cnftSeed),
+  });
+
+  await createTransmuter(cnftSeed, {
+    token_standard: "cnft",
+    collection: cnftCollection.mintAddress.toBase58(),
+    merkle_tree: outputTree.toBase58(),
+    method: "mint",
+    amount: 1,
+    mint_info: {
+      title: "Compressed output",
+      symbol: "CNFT",
+      uri: "https://arweave.net/qF9H_BBdjf-ZIR90_z5xXsSx8WiPB3-pHA8QTlg1oeI",
+    },
+  });
+
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    cnftSeed
+  );
+
+  assert.equal(
+    JSON.parse(transmuterStruct.account.outputs[0]).merkle_tree,
+    outputTree.toBase58()
+  );
+});
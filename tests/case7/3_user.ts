@@ -0,0 +1,226 @@
+import assert from "assert";
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import { creator, inputMetadata, inputTree, user } from "./1_init";
+import {
+  cnftCollection,
+  cnftSeed,
+  getAuth,
+  getTransmuter,
+  outputTree,
+  seed,
+} from "./2_transmuter";
+import {
+  TOKEN_METADATA_PROGRAM_ID,
+  cnftPrograms,
+  confirmTx,
+  getAssetId,
+  getBubblegumSigner,
+  getFirstLeafProof,
+  getLeafInfo,
+  getTreeConfig,
+  getvaultAuthStruct,
+  modifyComputeUnits,
+  toMetadataJson,
+} from "../utils";
+import { program } from "..";
+
+export const getVaultAuth = (seed: anchor.BN, vaultSeed: anchor.BN) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("vaultAuth"),
+      getTransmuter(seed).toBytes(),
+      user.publicKey.toBytes(),
+      vaultSeed.toBuffer().reverse(),
+    ],
+    program.programId
+  )[0];
+
+export const initVaultAuth = (seed: anchor.BN, vaultSeed: anchor.BN) =>
+  program.methods
+    .userInitVaultAuth(seed, vaultSeed)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      vaultAuth: getVaultAuth(seed, vaultSeed),
+      transmuter: getTransmuter(seed),
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+
+//The input tree only holds one leaf, its nonce and index are 0
+export const sendInputCnft = async (
+  seed: anchor.BN,
+  vaultSeed: anchor.BN,
+  metadataJson: string,
+  slotIndex: anchor.BN | null = null
+) => {
+  const leafInfo = await getLeafInfo(inputTree, inputMetadata, 0);
+
+  return program.methods
+    .userSendInputCnft(
+      seed,
+      vaultSeed,
+      leafInfo.root,
+      leafInfo.nonce,
+      leafInfo.index,
+      metadataJson,
+      slotIndex
+    )
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      transmuter: getTransmuter(seed),
+      vaultAuth: getVaultAuth(seed, vaultSeed),
+      treeConfig: getTreeConfig(inputTree),
+      merkleTree: inputTree,
+      ...cnftPrograms,
+    })
+    .remainingAccounts(getFirstLeafProof())
+    .signers([user])
+    .rpc();
+};
+
+const vaultSeed = new anchor.BN(randomBytes(8));
+
+it("should init the vault auth", async () => {
+  await initVaultAuth(seed, vaultSeed);
+});
+
+it("should fail to send a cnft outside the input collection", async () => {
+  try {
+    const metadataJson = toMetadataJson({
+      ...inputMetadata,
+      collection: { verified: true, key: PublicKey.unique() },
+    });
+
+    await sendInputCnft(seed, vaultSeed, metadataJson);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidInputAccount"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should send the cnft into the requested slot", async () => {
+  await sendInputCnft(
+    seed,
+    vaultSeed,
+    toMetadataJson(inputMetadata),
+    new anchor.BN(0)
+  ).then(confirmTx);
+
+  const vaultAuthStruct = await getvaultAuthStruct(
+    program,
+    getTransmuter(seed),
+    user.publicKey,
+    vaultSeed
+  );
+  assert.ok(
+    vaultAuthStruct.account.handledInputs[0].equals(getAssetId(inputTree, 0))
+  );
+});
+
+it("should fail to send a cnft already in the vault", async () => {
+  try {
+    await sendInputCnft(seed, vaultSeed, toMetadataJson(inputMetadata));
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("DuplicateInputAccount"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should cancel the cnft back to the user", async () => {
+  const leafInfo = await getLeafInfo(inputTree, inputMetadata, 0);
+
+  await program.methods
+    .userCancelInputCnft(seed, vaultSeed, leafInfo)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      transmuter: getTransmuter(seed),
+      vaultAuth: getVaultAuth(seed, vaultSeed),
+      treeConfig: getTreeConfig(inputTree),
+      merkleTree: inputTree,
+      ...cnftPrograms,
+    })
+    .remainingAccounts(getFirstLeafProof())
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+
+  const vaultAuthStruct = await getvaultAuthStruct(
+    program,
+    getTransmuter(seed),
+    user.publicKey,
+    vaultSeed
+  );
+  assert.equal(vaultAuthStruct.account.handledInputs[0], null);
+});
+
+export const cnftVaultSeed = new anchor.BN(randomBytes(8));
+
+const claimOutputCnft = (merkleTree: PublicKey) =>
+  program.methods
+    .userClaimOutputCnft(cnftSeed, cnftVaultSeed, [])
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      transmuter: getTransmuter(cnftSeed),
+      auth: getAuth(cnftSeed),
+      vaultAuth: getVaultAuth(cnftSeed, cnftVaultSeed),
+      treeConfig: getTreeConfig(merkleTree),
+      merkleTree,
+      collectionMint: cnftCollection.mintAddress,
+      collectionMetadata: cnftCollection.metadataAddress,
+      collectionEdition: cnftCollection.masterEditionAddress,
+      collectionAuthorityRecordPda: null,
+      bubblegumSigner: getBubblegumSigner(),
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      ...cnftPrograms,
+    })
+    .preInstructions([modifyComputeUnits])
+    .signers([user])
+    .rpc();
+
+it("should send the cnft to the cnft output transmuter", async () => {
+  await initVaultAuth(cnftSeed, cnftVaultSeed);
+
+  await sendInputCnft(
+    cnftSeed,
+    cnftVaultSeed,
+    toMetadataJson(inputMetadata)
+  ).then(confirmTx);
+});
+
+it("should fail to mint the cnft output into another tree", async () => {
+  try {
+    await claimOutputCnft(inputTree);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidMerkleTree"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should mint the cnft output into the delegated tree", async () => {
+  await claimOutputCnft(outputTree).then(confirmTx);
+
+  const vaultAuthStruct = await getvaultAuthStruct(
+    program,
+    getTransmuter(cnftSeed),
+    user.publicKey,
+    cnftVaultSeed
+  );
+  assert.ok(
+    vaultAuthStruct.account.handledOutputs[0].equals(getAssetId(outputTree, 0))
+  );
+  assert.ok(!vaultAuthStruct.account.creatorLocked);
+});
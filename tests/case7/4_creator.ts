@@ -0,0 +1,39 @@
+import assert from "assert";
+import * as anchor from "@coral-xyz/anchor";
+import { creator, inputMetadata, inputTree, user } from "./1_init";
+import { cnftSeed, getTransmuter } from "./2_transmuter";
+import { cnftVaultSeed, getVaultAuth } from "./3_user";
+import {
+  cnftPrograms,
+  confirmTx,
+  getFirstLeafProof,
+  getLeafInfo,
+  getTreeConfig,
+} from "../utils";
+import { program } from "..";
+
+it("should resolve the cnft input to the creator", async () => {
+  const leafInfo = await getLeafInfo(inputTree, inputMetadata, 0);
+
+  await program.methods
+    .creatorResolveInputCnft(cnftSeed, cnftVaultSeed, leafInfo)
+    .accounts({
+      cranker: creator.publicKey,
+      creator: creator.publicKey,
+      user: user.publicKey,
+      transmuter: getTransmuter(cnftSeed),
+      vaultAuth: getVaultAuth(cnftSeed, cnftVaultSeed),
+      treeConfig: getTreeConfig(inputTree),
+      merkleTree: inputTree,
+      ...cnftPrograms,
+    })
+    .remainingAccounts(getFirstLeafProof())
+    .signers([creator])
+    .rpc()
+    .then(confirmTx);
+
+  const vaultAuth = await anchor
+    .getProvider()
+    .connection.getAccountInfo(getVaultAuth(cnftSeed, cnftVaultSeed));
+  assert.equal(vaultAuth, null);
+});
@@ -0,0 +1,83 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  CreateNftOutput,
+  Metaplex,
+  keypairIdentity,
+  mockStorage,
+} from "@metaplex-foundation/js";
+import { confirmTxs } from "../utils";
+
+require("dotenv").config({ path: ".env" });
+
+// Set up our keys
+export const [creator, user] = [new Keypair(), new Keypair()];
+console.log(`creator: ${creator.publicKey}`);
+console.log(`user: ${user.publicKey}`);
+
+export const userMetaplex = Metaplex.make(anchor.getProvider().connection)
+  .use(keypairIdentity(user))
+  .use(mockStorage());
+
+export const creatorMetaplex = Metaplex.make(anchor.getProvider().connection)
+  .use(keypairIdentity(creator))
+  .use(mockStorage());
+
+export const uri =
+  "https://arweave.net/qF9H_BBdjf-ZIR90_z5xXsSx8WiPB3-pHA8QTlg1oeI";
+
+export let inputCollection: CreateNftOutput;
+
+//Every transmuter of this case burns one nft of the input collection
+export const mintInput = async (name: string): Promise<PublicKey> => {
+  const mint = await userMetaplex.nfts().create({
+    name,
+    symbol: "INPT",
+    sellerFeeBasisPoints: 500,
+    uri,
+    creators: [
+      {
+        address: creator.publicKey,
+        share: 100,
+      },
+    ],
+    collection: inputCollection.mintAddress,
+    isMutable: true,
+  });
+
+  await userMetaplex.nfts().verifyCollection({
+    mintAddress: mint.mintAddress,
+    collectionMintAddress: inputCollection.mintAddress,
+  });
+
+  return mint.mintAddress;
+};
+
+it("Airdrop", async () => {
+  await Promise.all(
+    [creator, user].map(async (key) => {
+      return await anchor
+        .getProvider()
+        .connection.requestAirdrop(
+          key.publicKey,
+          100 * anchor.web3.LAMPORTS_PER_SOL
+        );
+    })
+  ).then(confirmTxs);
+});
+
+it("Creates the input collection", async () => {
+  inputCollection = await userMetaplex.nfts().create({
+    name: "Input collection",
+    symbol: "INPT",
+    sellerFeeBasisPoints: 500,
+    uri,
+    creators: [
+      {
+        address: creator.publicKey,
+        share: 100,
+      },
+    ],
+    isMutable: true,
+  });
+});
@@ -0,0 +1,708 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { AccountMeta, PublicKey, SystemProgram } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import assert from "assert";
+import { CreateNftOutput, toBigNumber } from "@metaplex-foundation/js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID as associatedTokenProgram,
+  TOKEN_PROGRAM_ID as tokenProgram,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  TOKEN_DECIMALS,
+  WBA,
+  getMasterEdition,
+  getProgramAuthority,
+  getTransmuterStruct,
+} from "../utils";
+import {
+  creator,
+  creatorMetaplex,
+  inputCollection,
+  uri,
+  user,
+} from "./1_init";
+import { program, programId } from "..";
+
+require("dotenv").config({ path: ".env" });
+
+// Random seeds, one transmuter per output type
+export const printSeed = new BN(randomBytes(8));
+export const poolSeed = new BN(randomBytes(8));
+export const shuffleSeed = new BN(randomBytes(8));
+export const splMintSeed = new BN(randomBytes(8));
+export const solSeed = new BN(randomBytes(8));
+export const pinnedSeed = new BN(randomBytes(8));
+
+export const getTransmuter = (seed: BN) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("transmuter"),
+      creator.publicKey.toBytes(),
+      seed.toBuffer().reverse(),
+    ],
+    program.programId
+  )[0];
+
+export const getAuth = (seed: BN) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("auth"), getTransmuter(seed).toBytes()],
+    program.programId
+  )[0];
+
+export const setTransmuter = (seed: BN, transmuterConfig: object) =>
+  program.methods
+    .transmuterSet(seed, JSON.stringify(transmuterConfig))
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(seed),
+    })
+    .signers([creator])
+    .rpc();
+
+export const resumeTransmuter = (seed: BN) =>
+  program.methods
+    .transmuterResume(seed)
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(seed),
+    })
+    .signers([creator])
+    .rpc();
+
+//Creates a locked transmuter burning one nft of the input collection
+export const createTransmuter = async (seed: BN, outputLength = 1) => {
+  const owner = await getProgramAuthority(
+    anchor.getProvider().connection,
+    programId
+  );
+
+  const transmuterConfig = {
+    input_length: 1,
+    output_length: outputLength,
+  };
+
+  await program.methods
+    .transmuterCreate(seed, JSON.stringify(transmuterConfig))
+    .accounts({
+      creator: creator.publicKey,
+      auth: getAuth(seed),
+      transmuter: getTransmuter(seed),
+      systemProgram: SystemProgram.programId,
+      owner,
+      wba: WBA,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  const inputInfo = {
+    token_standard: "nft",
+    collection: inputCollection.mintAddress.toBase58(),
+    method: "burn",
+    amount: 1,
+  };
+
+  await program.methods
+    .transmuterSetInput(seed, JSON.stringify(inputInfo))
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(seed),
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+};
+
+export let masterEdition: CreateNftOutput;
+
+const setOutputPrint = () =>
+  program.methods
+    .transmuterSetOutputPrint(
+      printSeed,
+      JSON.stringify({
+        token_standard: "nft",
+        method: "print",
+        amount: 1,
+        mint: masterEdition.mintAddress.toBase58(),
+      })
+    )
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(printSeed),
+      auth: getAuth(printSeed),
+      masterMint: masterEdition.mintAddress,
+      masterEdition: masterEdition.masterEditionAddress,
+      creatorAta: masterEdition.tokenAddress,
+      authAta: getAssociatedTokenAddressSync(
+        masterEdition.mintAddress,
+        getAuth(printSeed),
+        true
+      ),
+      tokenProgram,
+      associatedTokenProgram,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([creator])
+    .rpc();
+
+it("creates a transmuter printing editions", async () => {
+  await createTransmuter(printSeed);
+
+  masterEdition = await creatorMetaplex.nfts().create({
+    name: "Master edition",
+    symbol: "MSTR",
+    sellerFeeBasisPoints: 500,
+    uri,
+    maxSupply: toBigNumber(10),
+  });
+  assert.ok(
+    masterEdition.masterEditionAddress.equals(
+      await getMasterEdition(masterEdition.mintAddress)
+    )
+  );
+});
+
+it("should fail to set a print output without a transmute max", async () => {
+  try {
+    await setOutputPrint();
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("PrintSupplyExceeded"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should deposit the master edition for a capped transmuter", async () => {
+  await setTransmuter(printSeed, {
+    input_length: 1,
+    output_length: 1,
+    transmute_max: 5,
+  });
+
+  await setOutputPrint();
+  await resumeTransmuter(printSeed);
+
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    printSeed
+  );
+  assert.equal(
+    JSON.parse(transmuterStruct.account.outputs[0]).mint,
+    masterEdition.mintAddress.toBase58()
+  );
+
+  const authAta = await anchor
+    .getProvider()
+    .connection.getTokenAccountBalance(
+      getAssociatedTokenAddressSync(
+        masterEdition.mintAddress,
+        getAuth(printSeed),
+        true
+      )
+    );
+  assert.equal(authAta.value.amount, "1");
+});
+
+const getPool = (seed: BN, poolItemSeed: BN) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("pool"),
+      getTransmuter(seed).toBytes(),
+      poolItemSeed.toBuffer().reverse(),
+    ],
+    program.programId
+  )[0];
+
+const poolItemSeed = new BN(randomBytes(8));
+export const pool = getPool(poolSeed, poolItemSeed);
+
+export let poolMints: PublicKey[] = [];
+
+const addToPool = async (seed: BN, itemSeed: BN, mint: PublicKey) => {
+  const creatorAta = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    creator,
+    mint,
+    creator.publicKey
+  );
+
+  await program.methods
+    .transmuterAddToPool(seed, itemSeed)
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(seed),
+      auth: getAuth(seed),
+      pool: getPool(seed, itemSeed),
+      mint,
+      creatorAta: creatorAta.address,
+      authAta: getAssociatedTokenAddressSync(mint, getAuth(seed), true),
+      tokenProgram,
+      associatedTokenProgram,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([creator])
+    .rpc();
+};
+
+const setOutputPool = async (seed: BN, itemSeed: BN, shuffle: boolean) => {
+  const outputInfo = {
+    token_standard: "nft",
+    method: "pool",
+    amount: 1,
+    pool: getPool(seed, itemSeed).toBase58(),
+  };
+
+  await program.methods
+    .transmuterSetOutputPool(
+      seed,
+      itemSeed,
+      shuffle,
+      JSON.stringify(outputInfo)
+    )
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(seed),
+      pool: getPool(seed, itemSeed),
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([creator])
+    .rpc();
+};
+
+const mintPoolItems = async (seed: BN, itemSeed: BN, count: number) => {
+  const mints: PublicKey[] = [];
+  for (let i = 0; i < count; i++) {
+    const item = await creatorMetaplex.nfts().create({
+      name: `Pool item #${i + 1}`,
+      symbol: "POOL",
+      sellerFeeBasisPoints: 500,
+      uri,
+    });
+    mints.push(item.mintAddress);
+
+    await addToPool(seed, itemSeed, item.mintAddress);
+  }
+  return mints;
+};
+
+export const withdrawFromPool = (
+  mint: PublicKey,
+  authority: PublicKey = creator.publicKey
+) =>
+  program.methods
+    .transmuterWithdrawPool(poolSeed, poolItemSeed)
+    .accounts({
+      creator: creator.publicKey,
+      authority,
+      transmuter: getTransmuter(poolSeed),
+      auth: getAuth(poolSeed),
+      pool,
+      mint,
+      authAta: getAssociatedTokenAddressSync(mint, getAuth(poolSeed), true),
+      creatorAta: getAssociatedTokenAddressSync(mint, authority),
+      tokenProgram,
+      associatedTokenProgram,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([creator])
+    .rpc();
+
+it("creates a transmuter handing out pool items", async () => {
+  await createTransmuter(poolSeed);
+  await setOutputPool(poolSeed, poolItemSeed, false);
+  await resumeTransmuter(poolSeed);
+
+  const poolStruct = await program.account.pool.fetch(pool);
+  assert.ok(poolStruct.transmuter.equals(getTransmuter(poolSeed)));
+  assert.equal(poolStruct.mints.length, 0);
+});
+
+it("should fail to add a fungible mint to the pool", async () => {
+  try {
+    const mint = await createMint(
+      anchor.getProvider().connection,
+      creator,
+      creator.publicKey,
+      null,
+      0
+    );
+    const creatorAta = await getOrCreateAssociatedTokenAccount(
+      anchor.getProvider().connection,
+      creator,
+      mint,
+      creator.publicKey
+    );
+    await mintTo(
+      anchor.getProvider().connection,
+      creator,
+      mint,
+      creatorAta.address,
+      creator,
+      2
+    );
+
+    await addToPool(poolSeed, poolItemSeed, mint);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidPool"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should add three nfts to the pool", async () => {
+  poolMints = await mintPoolItems(poolSeed, poolItemSeed, 3);
+
+  const poolStruct = await program.account.pool.fetch(pool);
+  assert.deepEqual(
+    poolStruct.mints.map((mint) => mint.toBase58()),
+    poolMints.map((mint) => mint.toBase58())
+  );
+});
+
+it("should fail to withdraw a pool item to another account", async () => {
+  try {
+    await withdrawFromPool(poolMints[2], user.publicKey);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("NotAuthority"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should withdraw the last pool item to the authority", async () => {
+  await withdrawFromPool(poolMints[2]);
+
+  const poolStruct = await program.account.pool.fetch(pool);
+  assert.equal(poolStruct.mints.length, 2);
+
+  const creatorAta = await getAccount(
+    anchor.getProvider().connection,
+    getAssociatedTokenAddressSync(poolMints[2], creator.publicKey)
+  );
+  assert.equal(Number(creatorAta.amount), 1);
+});
+
+const shuffleItemSeed = new BN(randomBytes(8));
+export const shuffledPool = getPool(shuffleSeed, shuffleItemSeed);
+
+export let shuffledMints: PublicKey[] = [];
+
+it("creates a transmuter handing out shuffled pool items", async () => {
+  await createTransmuter(shuffleSeed);
+  await setOutputPool(shuffleSeed, shuffleItemSeed, true);
+  shuffledMints = await mintPoolItems(shuffleSeed, shuffleItemSeed, 2);
+  await resumeTransmuter(shuffleSeed);
+
+  const poolStruct = await program.account.pool.fetch(shuffledPool);
+  assert.ok(poolStruct.shuffle);
+  assert.equal(poolStruct.mints.length, 2);
+});
+
+export let splMint: PublicKey;
+
+const setOutputSplMint = (amount: number) =>
+  program.methods
+    .transmuterSetOutputSplMint(
+      splMintSeed,
+      JSON.stringify({
+        token_standard: "spl",
+        method: "mint",
+        amount,
+        mint: splMint.toBase58(),
+        max_supply: 150 * TOKEN_DECIMALS,
+      })
+    )
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(splMintSeed),
+      auth: getAuth(splMintSeed),
+      mint: splMint,
+      tokenProgram,
+    })
+    .signers([creator])
+    .rpc();
+
+it("creates a transmuter minting spl tokens on demand", async () => {
+  await createTransmuter(splMintSeed);
+
+  splMint = await createMint(
+    anchor.getProvider().connection,
+    creator,
+    creator.publicKey,
+    null,
+    6
+  );
+});
+
+it("should fail to set an spl mint output minting nothing", async () => {
+  try {
+    await setOutputSplMint(0);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidAmount"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should hand the mint authority to the transmuter", async () => {
+  await setOutputSplMint(100 * TOKEN_DECIMALS);
+  await resumeTransmuter(splMintSeed);
+
+  const mint = await getMint(anchor.getProvider().connection, splMint);
+  assert.ok(mint.mintAuthority.equals(getAuth(splMintSeed)));
+});
+
+export const getEscrow = (seed: BN, escrowSeed: BN) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("solEscrow"),
+      getTransmuter(seed).toBytes(),
+      escrowSeed.toBuffer().reverse(),
+    ],
+    program.programId
+  )[0];
+
+const escrowSeed = new BN(randomBytes(8));
+
+export const escrow = getEscrow(solSeed, escrowSeed);
+
+const halfSol = anchor.web3.LAMPORTS_PER_SOL / 2;
+
+const setOutputSol = (
+  seed: BN,
+  escrowSeed: BN,
+  escrowKey: PublicKey,
+  amount: number,
+  deposit: number
+) =>
+  program.methods
+    .transmuterSetOutputSol(
+      seed,
+      escrowSeed,
+      JSON.stringify({
+        token_standard: "sol",
+        method: "transfer",
+        amount,
+        escrow: escrowKey.toBase58(),
+      }),
+      new BN(deposit)
+    )
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(seed),
+      escrow: getEscrow(seed, escrowSeed),
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([creator])
+    .rpc();
+
+const addOutputSol = (amount: number) =>
+  program.methods
+    .transmuterAddOutputSol(solSeed, escrowSeed, new BN(amount))
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(solSeed),
+      escrow,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([creator])
+    .rpc();
+
+it("creates a transmuter paying sol out of an escrow", async () => {
+  await createTransmuter(solSeed);
+});
+
+it("should fail to set a sol output pointing at another escrow", async () => {
+  try {
+    await setOutputSol(
+      solSeed,
+      escrowSeed,
+      creator.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL,
+      halfSol
+    );
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidEscrow"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should set a sol output funding its escrow", async () => {
+  await setOutputSol(
+    solSeed,
+    escrowSeed,
+    escrow,
+    anchor.web3.LAMPORTS_PER_SOL,
+    halfSol
+  );
+
+  const escrowStruct = await program.account.solEscrow.fetch(escrow);
+  assert.ok(escrowStruct.transmuter.equals(getTransmuter(solSeed)));
+});
+
+it("should fail to top up the escrow with nothing", async () => {
+  try {
+    await addOutputSol(0);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidAmount"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should top up the escrow to cover one claim", async () => {
+  const balanceBefore = await anchor
+    .getProvider()
+    .connection.getBalance(escrow);
+
+  await addOutputSol(halfSol);
+  await resumeTransmuter(solSeed);
+
+  const balanceAfter = await anchor.getProvider().connection.getBalance(escrow);
+  assert.equal(balanceAfter - balanceBefore, halfSol);
+});
+
+const pinnedEscrowSeeds = [0, 1].map(() => new BN(randomBytes(8)));
+
+export const pinnedEscrows = pinnedEscrowSeeds.map((escrowSeed) =>
+  getEscrow(pinnedSeed, escrowSeed)
+);
+
+export const pinnedAmount = anchor.web3.LAMPORTS_PER_SOL / 10;
+
+it("creates a transmuter paying two sol outputs out of their own escrows", async () => {
+  await createTransmuter(pinnedSeed, 2);
+
+  for (const [index, escrowSeed] of pinnedEscrowSeeds.entries()) {
+    await setOutputSol(
+      pinnedSeed,
+      escrowSeed,
+      pinnedEscrows[index],
+      pinnedAmount,
+      pinnedAmount
+    );
+  }
+  await resumeTransmuter(pinnedSeed);
+
+  const transmuter = await program.account.transmuter.fetch(
+    getTransmuter(pinnedSeed)
+  );
+  assert.equal(transmuter.outputs.length, 2);
+});
+
+const closeSeed = new BN(randomBytes(8));
+const closeEscrowSeed = new BN(randomBytes(8));
+const closeItemSeed = new BN(randomBytes(8));
+
+const closeTransmuter = (remainingAccounts: AccountMeta[]) =>
+  program.methods
+    .transmuterClose()
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(closeSeed),
+      auth: getAuth(closeSeed),
+      tokenProgram,
+    })
+    .remainingAccounts(remainingAccounts)
+    .signers([creator])
+    .rpc();
+
+let closeItem: PublicKey;
+
+it("should leave escrows behind when their outputs are removed", async () => {
+  await createTransmuter(closeSeed, 2);
+  await setOutputSol(
+    closeSeed,
+    closeEscrowSeed,
+    getEscrow(closeSeed, closeEscrowSeed),
+    pinnedAmount,
+    pinnedAmount
+  );
+  await setOutputPool(closeSeed, closeItemSeed, false);
+  [closeItem] = await mintPoolItems(closeSeed, closeItemSeed, 1);
+
+  for (let i = 0; i < 2; i++) {
+    await program.methods
+      .transmuterRemoveOutput(closeSeed, new BN(0))
+      .accounts({
+        creator: creator.publicKey,
+        transmuter: getTransmuter(closeSeed),
+      })
+      .signers([creator])
+      .rpc();
+  }
+
+  const transmuter = await program.account.transmuter.fetch(
+    getTransmuter(closeSeed)
+  );
+  assert.equal(transmuter.outputs.length, 0);
+});
+
+it("should fail to close the transmuter without its pools", async () => {
+  try {
+    await closeTransmuter([]);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidPool"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should drain the escrows of removed outputs on close", async () => {
+  const authAta = getAssociatedTokenAddressSync(
+    closeItem,
+    getAuth(closeSeed),
+    true
+  );
+  const creatorAta = getAssociatedTokenAddressSync(
+    closeItem,
+    creator.publicKey
+  );
+
+  //Pool, its item atas, then the sol escrow
+  await closeTransmuter([
+    {
+      pubkey: getPool(closeSeed, closeItemSeed),
+      isSigner: false,
+      isWritable: true,
+    },
+    { pubkey: authAta, isSigner: false, isWritable: true },
+    { pubkey: creatorAta, isSigner: false, isWritable: true },
+    {
+      pubkey: getEscrow(closeSeed, closeEscrowSeed),
+      isSigner: false,
+      isWritable: true,
+    },
+  ]);
+
+  const connection = anchor.getProvider().connection;
+  for (const key of [
+    getTransmuter(closeSeed),
+    getPool(closeSeed, closeItemSeed),
+    getEscrow(closeSeed, closeEscrowSeed),
+    authAta,
+  ]) {
+    assert.equal(await connection.getAccountInfo(key), null);
+  }
+
+  const item = await getAccount(connection, creatorAta);
+  assert.equal(Number(item.amount), 1);
+});
@@ -0,0 +1,477 @@
+import assert from "assert";
+import * as anchor from "@coral-xyz/anchor";
+import { AccountMeta, PublicKey, SystemProgram } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID as associatedTokenProgram,
+  TOKEN_PROGRAM_ID as tokenProgram,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { creator, mintInput, user } from "./1_init";
+import {
+  escrow,
+  getAuth,
+  getTransmuter,
+  masterEdition,
+  pinnedEscrows,
+  pinnedSeed,
+  pool,
+  poolMints,
+  poolSeed,
+  printSeed,
+  shuffleSeed,
+  shuffledMints,
+  shuffledPool,
+  solSeed,
+  splMint,
+  splMintSeed,
+  withdrawFromPool,
+} from "./2_transmuter";
+import {
+  TOKEN_DECIMALS,
+  TOKEN_METADATA_PROGRAM_ID,
+  confirmTx,
+  getEditionMarker,
+  getMasterEdition,
+  getMetadata,
+  modifyComputeUnits,
+} from "../utils";
+import { program } from "..";
+
+export const getVaultAuth = (seed: anchor.BN, vaultSeed: anchor.BN) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("vaultAuth"),
+      getTransmuter(seed).toBytes(),
+      user.publicKey.toBytes(),
+      vaultSeed.toBuffer().reverse(),
+    ],
+    program.programId
+  )[0];
+
+//Opens a vault and sends a freshly minted input nft into it
+export const initVaultAndSendInput = async (
+  seed: anchor.BN,
+  vaultSeed: anchor.BN,
+  remainingAccounts: AccountMeta[] = []
+) => {
+  const vaultAuth = getVaultAuth(seed, vaultSeed);
+
+  await program.methods
+    .userInitVaultAuth(seed, vaultSeed)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      vaultAuth,
+      transmuter: getTransmuter(seed),
+      systemProgram: SystemProgram.programId,
+    })
+    .remainingAccounts(remainingAccounts)
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+
+  const inputMint = await mintInput("Input");
+
+  const vault = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    user,
+    inputMint,
+    vaultAuth,
+    true
+  );
+
+  await program.methods
+    .userSendInput(seed, vaultSeed, null)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      mint: inputMint,
+      ata: getAssociatedTokenAddressSync(inputMint, user.publicKey),
+      metadata: await getMetadata(inputMint),
+      vaultAuth,
+      vault: vault.address,
+      tokenProgram,
+      transmuter: getTransmuter(seed),
+    })
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+
+  return inputMint;
+};
+
+//New output mints are handed to the auth PDA
+export const createOutputMint = async (seed: anchor.BN) => {
+  const mint = await createMint(
+    anchor.getProvider().connection,
+    user,
+    getAuth(seed),
+    getAuth(seed),
+    0
+  );
+
+  const ata = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    user,
+    mint,
+    user.publicKey,
+    true
+  );
+
+  return { mint, ata: ata.address };
+};
+
+const printVaultSeed = new anchor.BN(randomBytes(8));
+
+const claimPrint = async (withSource: boolean) => {
+  const { mint, ata } = await createOutputMint(printSeed);
+  const auth = getAuth(printSeed);
+  const masterMint = masterEdition.mintAddress;
+
+  await program.methods
+    .userClaimOutputNft(printSeed, printVaultSeed, [])
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      transmuter: getTransmuter(printSeed),
+      auth,
+      vaultAuth: getVaultAuth(printSeed, printVaultSeed),
+      mint,
+      ata,
+      metadata: await getMetadata(mint),
+      masterEdition: await getMasterEdition(mint),
+      tokenProgram,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      associatedTokenProgram,
+      systemProgram: SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      sysvarInstructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      sourceMetadata: withSource ? await getMetadata(masterMint) : null,
+      sourceEdition: withSource ? await getMasterEdition(masterMint) : null,
+      sourceAta: withSource
+        ? getAssociatedTokenAddressSync(masterMint, auth, true)
+        : null,
+      editionMarker: withSource ? await getEditionMarker(masterMint, 1) : null,
+    })
+    .preInstructions([modifyComputeUnits])
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+
+  return mint;
+};
+
+it("should send an input to the print transmuter", async () => {
+  await initVaultAndSendInput(printSeed, printVaultSeed);
+});
+
+it("should fail to claim a print without the master edition", async () => {
+  try {
+    await claimPrint(false);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidMasterEdition"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should claim the first print of the master edition", async () => {
+  const mint = await claimPrint(true);
+
+  const ata = await anchor
+    .getProvider()
+    .connection.getTokenAccountBalance(
+      getAssociatedTokenAddressSync(mint, user.publicKey)
+    );
+  assert.equal(ata.value.amount, "1");
+
+  //Master edition supply follows the one byte account key
+  const masterEditionAccount = await anchor
+    .getProvider()
+    .connection.getAccountInfo(
+      await getMasterEdition(masterEdition.mintAddress)
+    );
+  assert.equal(masterEditionAccount.data.readBigUInt64LE(1), BigInt(1));
+});
+
+const poolVaultSeeds = [0, 1, 2].map(() => new anchor.BN(randomBytes(8)));
+
+const claimPoolItem = (
+  seed: anchor.BN,
+  vaultSeed: anchor.BN,
+  poolKey: PublicKey,
+  mint: PublicKey
+) =>
+  program.methods
+    .userClaimOutputPool(seed, vaultSeed)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      transmuter: getTransmuter(seed),
+      auth: getAuth(seed),
+      vaultAuth: getVaultAuth(seed, vaultSeed),
+      pool: poolKey,
+      mint,
+      authAta: getAssociatedTokenAddressSync(mint, getAuth(seed), true),
+      userAta: getAssociatedTokenAddressSync(mint, user.publicKey),
+      tokenProgram,
+      associatedTokenProgram,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([user])
+    .rpc();
+
+const poolAccounts = (poolKey: PublicKey): AccountMeta[] => [
+  { pubkey: poolKey, isSigner: false, isWritable: true },
+];
+
+it("should reserve a pool item for each new vault", async () => {
+  for (const vaultSeed of poolVaultSeeds.slice(0, 2)) {
+    await initVaultAndSendInput(poolSeed, vaultSeed, poolAccounts(pool));
+  }
+
+  const poolStruct = await program.account.pool.fetch(pool);
+  assert.equal(poolStruct.mints.length, 2);
+  assert.equal(poolStruct.reserved, 2);
+});
+
+it("should fail to open a vault once every pool item is reserved", async () => {
+  try {
+    await initVaultAndSendInput(
+      poolSeed,
+      poolVaultSeeds[2],
+      poolAccounts(pool)
+    );
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("PoolDepleted"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should fail to withdraw a reserved pool item", async () => {
+  try {
+    await withdrawFromPool(poolMints[1]);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("PoolDepleted"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should fail to claim a pool item out of order", async () => {
+  try {
+    await claimPoolItem(poolSeed, poolVaultSeeds[0], pool, poolMints[1]);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidPool"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should claim the next pool item", async () => {
+  await claimPoolItem(poolSeed, poolVaultSeeds[0], pool, poolMints[0]).then(
+    confirmTx
+  );
+
+  const userAta = await getAccount(
+    anchor.getProvider().connection,
+    getAssociatedTokenAddressSync(poolMints[0], user.publicKey)
+  );
+  assert.equal(Number(userAta.amount), 1);
+
+  const poolStruct = await program.account.pool.fetch(pool);
+  assert.equal(poolStruct.mints.length, 1);
+  assert.equal(poolStruct.reserved, 1);
+});
+
+const shuffleVaultSeed = new anchor.BN(randomBytes(8));
+
+//Index of the item the vault committed to when it opened
+let pickIndex: number;
+
+it("should fail to open a shuffled vault without the slot hashes", async () => {
+  try {
+    await initVaultAndSendInput(
+      shuffleSeed,
+      shuffleVaultSeed,
+      poolAccounts(shuffledPool)
+    );
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidPool"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should commit a pick seed when opening a shuffled pool vault", async () => {
+  await initVaultAndSendInput(shuffleSeed, shuffleVaultSeed, [
+    ...poolAccounts(shuffledPool),
+    {
+      pubkey: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      isSigner: false,
+      isWritable: false,
+    },
+  ]);
+
+  const vaultAuth = await program.account.vaultAuth.fetch(
+    getVaultAuth(shuffleSeed, shuffleVaultSeed)
+  );
+  pickIndex = vaultAuth.pickSeed.mod(new anchor.BN(2)).toNumber();
+});
+
+it("should fail to claim another item than the picked one", async () => {
+  try {
+    await claimPoolItem(
+      shuffleSeed,
+      shuffleVaultSeed,
+      shuffledPool,
+      shuffledMints[1 - pickIndex]
+    );
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidPool"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should claim the picked shuffled item", async () => {
+  const mint = shuffledMints[pickIndex];
+  await claimPoolItem(shuffleSeed, shuffleVaultSeed, shuffledPool, mint).then(
+    confirmTx
+  );
+
+  const userAta = await getAccount(
+    anchor.getProvider().connection,
+    getAssociatedTokenAddressSync(mint, user.publicKey)
+  );
+  assert.equal(Number(userAta.amount), 1);
+
+  const poolStruct = await program.account.pool.fetch(shuffledPool);
+  assert.deepEqual(
+    poolStruct.mints.map((mint) => mint.toBase58()),
+    [shuffledMints[1 - pickIndex].toBase58()]
+  );
+  assert.equal(poolStruct.reserved, 0);
+});
+
+const claimSplMint = async (vaultSeed: anchor.BN) => {
+  const userAta = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    user,
+    splMint,
+    user.publicKey
+  );
+
+  await program.methods
+    .userClaimOutputSpl(splMintSeed, vaultSeed)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      transmuter: getTransmuter(splMintSeed),
+      auth: getAuth(splMintSeed),
+      vaultAuth: getVaultAuth(splMintSeed, vaultSeed),
+      mint: splMint,
+      authAta: null,
+      userAta: userAta.address,
+      tokenProgram,
+    })
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+
+  return userAta.address;
+};
+
+it("should mint spl tokens to the user", async () => {
+  const vaultSeed = new anchor.BN(randomBytes(8));
+  await initVaultAndSendInput(splMintSeed, vaultSeed);
+
+  const userAta = await claimSplMint(vaultSeed);
+
+  const userAtaAfter = await getAccount(
+    anchor.getProvider().connection,
+    userAta
+  );
+  assert.equal(Number(userAtaAfter.amount), 100 * TOKEN_DECIMALS);
+});
+
+it("should fail to mint past the supply cap", async () => {
+  try {
+    const vaultSeed = new anchor.BN(randomBytes(8));
+    await initVaultAndSendInput(splMintSeed, vaultSeed);
+
+    await claimSplMint(vaultSeed);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("SupplyCapReached"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+export const claimSol = (
+  vaultSeed: anchor.BN,
+  seed = solSeed,
+  escrowKey = escrow
+) =>
+  program.methods
+    .userClaimOutputSol(seed, vaultSeed)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      transmuter: getTransmuter(seed),
+      vaultAuth: getVaultAuth(seed, vaultSeed),
+      escrow: escrowKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+
+it("should pay the sol output out of the escrow", async () => {
+  const vaultSeed = new anchor.BN(randomBytes(8));
+  await initVaultAndSendInput(solSeed, vaultSeed);
+
+  const escrowBefore = await anchor.getProvider().connection.getBalance(escrow);
+  await claimSol(vaultSeed);
+  const escrowAfter = await anchor.getProvider().connection.getBalance(escrow);
+
+  assert.equal(escrowBefore - escrowAfter, anchor.web3.LAMPORTS_PER_SOL);
+});
+
+it("should fail to claim from an emptied escrow", async () => {
+  try {
+    const vaultSeed = new anchor.BN(randomBytes(8));
+    await initVaultAndSendInput(solSeed, vaultSeed);
+
+    await claimSol(vaultSeed);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InsufficientEscrow"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+export const pinnedVaultSeed = new anchor.BN(randomBytes(8));
+
+it("should claim the first of two sol outputs", async () => {
+  await initVaultAndSendInput(pinnedSeed, pinnedVaultSeed);
+  await claimSol(pinnedVaultSeed, pinnedSeed, pinnedEscrows[0]);
+
+  const transmuter = await program.account.transmuter.fetch(
+    getTransmuter(pinnedSeed)
+  );
+  assert.equal(transmuter.partialVaults.toNumber(), 1);
+});
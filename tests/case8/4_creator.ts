@@ -0,0 +1,199 @@
+import assert from "assert";
+import * as anchor from "@coral-xyz/anchor";
+import { AccountMeta, Keypair, PublicKey } from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID as tokenProgram,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { creator, user } from "./1_init";
+import {
+  escrow,
+  getAuth,
+  getTransmuter,
+  pinnedEscrows,
+  pinnedSeed,
+  pool,
+  poolMints,
+  poolSeed,
+  solSeed,
+  splMint,
+  splMintSeed,
+} from "./2_transmuter";
+import { claimSol, pinnedVaultSeed } from "./3_user";
+import { program } from "..";
+
+const revokeSplMint = (signer: Keypair) =>
+  program.methods
+    .transmuterRevokeOutputSplMint(splMintSeed)
+    .accounts({
+      creator: signer.publicKey,
+      transmuter: getTransmuter(splMintSeed),
+      auth: getAuth(splMintSeed),
+      mint: splMint,
+      tokenProgram,
+    })
+    .signers([signer])
+    .rpc();
+
+it("should fail to revoke the mint without the withdraw role", async () => {
+  try {
+    await revokeSplMint(user);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("MissingRole"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should hand the mint authority back to the creator", async () => {
+  await revokeSplMint(creator);
+
+  const mint = await getMint(anchor.getProvider().connection, splMint);
+  assert.ok(mint.mintAuthority.equals(creator.publicKey));
+});
+
+const cancelSol = (authority: Keypair, seed = solSeed, escrowKey = escrow) =>
+  program.methods
+    .transmuterCancelOutputSol(seed)
+    .accounts({
+      creator: creator.publicKey,
+      authority: authority.publicKey,
+      transmuter: getTransmuter(seed),
+      escrow: escrowKey,
+    })
+    .signers([creator])
+    .rpc();
+
+it("should fail to close the escrow to another account", async () => {
+  try {
+    await cancelSol(user);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("NotAuthority"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should close the escrow to the authority", async () => {
+  await cancelSol(creator);
+
+  const escrowAccount = await anchor
+    .getProvider()
+    .connection.getAccountInfo(escrow);
+  assert.equal(escrowAccount, null);
+});
+
+it("should fail to close an escrow a partially claimed vault still pays from", async () => {
+  try {
+    await cancelSol(creator, pinnedSeed, pinnedEscrows[1]);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("VaultsOpen"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should close the escrow once the vault claimed every output", async () => {
+  await claimSol(pinnedVaultSeed, pinnedSeed, pinnedEscrows[1]);
+
+  const transmuter = await program.account.transmuter.fetch(
+    getTransmuter(pinnedSeed)
+  );
+  assert.equal(transmuter.partialVaults.toNumber(), 0);
+
+  await cancelSol(creator, pinnedSeed, pinnedEscrows[1]);
+
+  const escrowAccount = await anchor
+    .getProvider()
+    .connection.getAccountInfo(pinnedEscrows[1]);
+  assert.equal(escrowAccount, null);
+});
+
+const cancelSpl = (mint: PublicKey, pools: AccountMeta[]) =>
+  program.methods
+    .transmuterCancelOutputSpl(poolSeed)
+    .accounts({
+      creator: creator.publicKey,
+      transmuter: getTransmuter(poolSeed),
+      auth: getAuth(poolSeed),
+      creatorAta: getAssociatedTokenAddressSync(mint, creator.publicKey),
+      mint,
+      authAta: getAssociatedTokenAddressSync(mint, getAuth(poolSeed), true),
+      tokenProgram,
+    })
+    .remainingAccounts(pools)
+    .signers([creator])
+    .rpc();
+
+const poolAccounts = [{ pubkey: pool, isSigner: false, isWritable: false }];
+
+it("should fail to withdraw a pool item as a spl output", async () => {
+  try {
+    await cancelSpl(poolMints[1], poolAccounts);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidPool"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+let strayMint: PublicKey;
+
+it("should fail to withdraw spl tokens without every pool", async () => {
+  strayMint = await createMint(
+    anchor.getProvider().connection,
+    creator,
+    creator.publicKey,
+    null,
+    0
+  );
+  await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    creator,
+    strayMint,
+    creator.publicKey
+  );
+  const authAta = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    creator,
+    strayMint,
+    getAuth(poolSeed),
+    true
+  );
+  await mintTo(
+    anchor.getProvider().connection,
+    creator,
+    strayMint,
+    authAta.address,
+    creator,
+    10
+  );
+
+  try {
+    await cancelSpl(strayMint, []);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidPool"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should withdraw spl tokens that are not pool items", async () => {
+  await cancelSpl(strayMint, poolAccounts);
+
+  const creatorAta = await getAccount(
+    anchor.getProvider().connection,
+    getAssociatedTokenAddressSync(strayMint, creator.publicKey)
+  );
+  assert.equal(Number(creatorAta.amount), 10);
+});
@@ -0,0 +1,9 @@
+import { importTest } from "../utils/helpers";
+
+// print, pool, minted spl and sol outputs
+describe("Transmuter case 8", () => {
+  importTest("Set up tests", `${__dirname}/1_init`);
+  importTest("Transmuter tests", `${__dirname}/2_transmuter`);
+  importTest("User tests", `${__dirname}/3_user`);
+  importTest("Creator tests", `${__dirname}/4_creator`);
+});
@@ -0,0 +1,130 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import assert from "assert";
+import { WBA, getProgramAuthority, getTransmuterStruct } from "../utils";
+import { creator, inputCollection } from "./1_init";
+import { program, programId } from "..";
+
+require("dotenv").config({ path: ".env" });
+
+// Random seed
+export const seed = new BN(randomBytes(8));
+
+//Vaults expire quickly so the claimed one can be refunded within the test run
+export const VAULT_TTL_SECONDS = 3;
+
+export const transmuter = PublicKey.findProgramAddressSync(
+  [
+    Buffer.from("transmuter"),
+    creator.publicKey.toBytes(),
+    seed.toBuffer().reverse(),
+  ],
+  program.programId
+)[0];
+
+const auth = PublicKey.findProgramAddressSync(
+  [Buffer.from("auth"), transmuter.toBytes()],
+  program.programId
+)[0];
+
+const escrowSeed = new BN(randomBytes(8));
+
+export const escrow = PublicKey.findProgramAddressSync(
+  [
+    Buffer.from("solEscrow"),
+    transmuter.toBytes(),
+    escrowSeed.toBuffer().reverse(),
+  ],
+  program.programId
+)[0];
+
+//Both inputs burn any nft of the input collection
+it("creates a transmuter taking two inputs", async () => {
+  const owner = await getProgramAuthority(
+    anchor.getProvider().connection,
+    programId
+  );
+
+  await program.methods
+    .transmuterCreate(
+      seed,
+      JSON.stringify({
+        input_length: 2,
+        output_length: 1,
+        vault_ttl_seconds: VAULT_TTL_SECONDS,
+      })
+    )
+    .accounts({
+      creator: creator.publicKey,
+      auth,
+      transmuter,
+      systemProgram: SystemProgram.programId,
+      owner,
+      wba: WBA,
+    })
+    .signers([creator])
+    .rpc({
+      skipPreflight: true,
+    });
+
+  const inputInfo = {
+    token_standard: "nft",
+    collection: inputCollection.mintAddress.toBase58(),
+    method: "burn",
+    amount: 1,
+  };
+
+  for (let i = 0; i < 2; i++) {
+    await program.methods
+      .transmuterSetInput(seed, JSON.stringify(inputInfo))
+      .accounts({
+        creator: creator.publicKey,
+        transmuter,
+      })
+      .signers([creator])
+      .rpc({
+        skipPreflight: true,
+      });
+  }
+
+  const outputInfo = {
+    token_standard: "sol",
+    method: "transfer",
+    amount: 1000000,
+    escrow: escrow.toBase58(),
+  };
+
+  await program.methods
+    .transmuterSetOutputSol(
+      seed,
+      escrowSeed,
+      JSON.stringify(outputInfo),
+      new BN(anchor.web3.LAMPORTS_PER_SOL)
+    )
+    .accounts({
+      creator: creator.publicKey,
+      transmuter,
+      escrow,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([creator])
+    .rpc();
+
+  await program.methods
+    .transmuterResume(seed)
+    .accounts({
+      creator: creator.publicKey,
+      transmuter,
+    })
+    .signers([creator])
+    .rpc();
+
+  const transmuterStruct = await getTransmuterStruct(
+    program,
+    creator.publicKey,
+    seed
+  );
+  assert.equal(transmuterStruct.account.inputs.length, 2);
+});
@@ -0,0 +1,340 @@
+import assert from "assert";
+import * as anchor from "@coral-xyz/anchor";
+import { AccountMeta, PublicKey, SystemProgram } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID as associatedTokenProgram,
+  TOKEN_PROGRAM_ID as tokenProgram,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { creator, mintInput, user } from "./1_init";
+import { escrow, seed, transmuter } from "./2_transmuter";
+import {
+  TOKEN_METADATA_PROGRAM_ID,
+  confirmTx,
+  getMasterEdition,
+  getMetadata,
+} from "../utils";
+import { program } from "..";
+
+export const getVaultAuth = (vaultSeed: anchor.BN) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("vaultAuth"),
+      transmuter.toBytes(),
+      user.publicKey.toBytes(),
+      vaultSeed.toBuffer().reverse(),
+    ],
+    program.programId
+  )[0];
+
+export const initVaultAuth = (vaultSeed: anchor.BN) =>
+  program.methods
+    .userInitVaultAuth(seed, vaultSeed)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      vaultAuth: getVaultAuth(vaultSeed),
+      transmuter,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+
+export const getVault = (vaultSeed: anchor.BN, mint: PublicKey) =>
+  getAssociatedTokenAddressSync(mint, getVaultAuth(vaultSeed), true);
+
+export const getHandledInputs = async (vaultSeed: anchor.BN) => {
+  const vaultAuth = await program.account.vaultAuth.fetch(
+    getVaultAuth(vaultSeed)
+  );
+  return vaultAuth.handledInputs.map((input) => input?.toBase58() ?? null);
+};
+
+export const batchVaultSeed = new anchor.BN(randomBytes(8));
+
+export let batchMints: PublicKey[] = [];
+
+//Batched sends read (mint, ata, metadata, vault) groups, vaults must exist
+const sendInputs = async (mints: PublicKey[]) => {
+  const remainingAccounts: AccountMeta[] = [];
+  for (const mint of mints) {
+    const vault = await getOrCreateAssociatedTokenAccount(
+      anchor.getProvider().connection,
+      user,
+      mint,
+      getVaultAuth(batchVaultSeed),
+      true
+    );
+
+    remainingAccounts.push(
+      { pubkey: mint, isSigner: false, isWritable: false },
+      {
+        pubkey: getAssociatedTokenAddressSync(mint, user.publicKey),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: await getMetadata(mint), isSigner: false, isWritable: false },
+      { pubkey: vault.address, isSigner: false, isWritable: true }
+    );
+  }
+
+  await program.methods
+    .userSendInputs(seed, batchVaultSeed)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      transmuter,
+      vaultAuth: getVaultAuth(batchVaultSeed),
+      tokenProgram,
+    })
+    .remainingAccounts(remainingAccounts)
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+};
+
+it("should open a vault and mint two inputs", async () => {
+  await initVaultAuth(batchVaultSeed);
+
+  batchMints = [await mintInput("Input #1"), await mintInput("Input #2")];
+});
+
+it("should fail to send the same input twice in one batch", async () => {
+  try {
+    await sendInputs([batchMints[0], batchMints[0]]);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("DuplicateInputAccount"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should send both inputs in one instruction", async () => {
+  await sendInputs(batchMints);
+
+  assert.deepEqual(
+    await getHandledInputs(batchVaultSeed),
+    batchMints.map((mint) => mint.toBase58())
+  );
+
+  for (const mint of batchMints) {
+    const vault = await getAccount(
+      anchor.getProvider().connection,
+      getVault(batchVaultSeed, mint)
+    );
+    assert.equal(Number(vault.amount), 1);
+  }
+});
+
+export const slotVaultSeed = new anchor.BN(randomBytes(8));
+
+export let slotMint: PublicKey;
+
+//The vault ata is created by the program on send
+const sendInput = async (mint: PublicKey, slotIndex: anchor.BN | null) =>
+  program.methods
+    .userSendInput(seed, slotVaultSeed, slotIndex)
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      mint,
+      ata: getAssociatedTokenAddressSync(mint, user.publicKey),
+      metadata: await getMetadata(mint),
+      transmuter,
+      vaultAuth: getVaultAuth(slotVaultSeed),
+      vault: getVault(slotVaultSeed, mint),
+      tokenProgram,
+      associatedTokenProgram,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+
+it("should send an input to the requested slot only", async () => {
+  await initVaultAuth(slotVaultSeed);
+  slotMint = await mintInput("Input #3");
+
+  await sendInput(slotMint, new anchor.BN(1));
+
+  assert.deepEqual(await getHandledInputs(slotVaultSeed), [
+    null,
+    slotMint.toBase58(),
+  ]);
+
+  const vault = await getAccount(
+    anchor.getProvider().connection,
+    getVault(slotVaultSeed, slotMint)
+  );
+  assert.ok(vault.owner.equals(getVaultAuth(slotVaultSeed)));
+  assert.equal(Number(vault.amount), 1);
+});
+
+it("should fail to send an input to a taken slot", async () => {
+  try {
+    await sendInput(await mintInput("Input #4"), new anchor.BN(1));
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidIndex"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should fail to send an input already in the vault", async () => {
+  try {
+    await sendInput(slotMint, null);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("DuplicateInputAccount"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+const abortVault = (vaultSeed: anchor.BN, mints: PublicKey[]) =>
+  program.methods
+    .userAbortVault(seed, vaultSeed, [])
+    .accounts({
+      creator: creator.publicKey,
+      user: user.publicKey,
+      transmuter,
+      vaultAuth: getVaultAuth(vaultSeed),
+      tokenProgram,
+      systemProgram: SystemProgram.programId,
+      coreProgram: null,
+      bubblegumProgram: null,
+      logWrapper: null,
+      compressionProgram: null,
+    })
+    .remainingAccounts(
+      mints.flatMap((mint) => [
+        { pubkey: mint, isSigner: false, isWritable: false },
+        {
+          pubkey: getVault(vaultSeed, mint),
+          isSigner: false,
+          isWritable: true,
+        },
+        {
+          pubkey: getAssociatedTokenAddressSync(mint, user.publicKey),
+          isSigner: false,
+          isWritable: true,
+        },
+      ])
+    )
+    .signers([user])
+    .rpc()
+    .then(confirmTx);
+
+it("should fail to abort without returning every input", async () => {
+  try {
+    await abortVault(slotVaultSeed, []);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidInputAccount"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should abort the vault and return its input", async () => {
+  await abortVault(slotVaultSeed, [slotMint]);
+
+  const userAta = await getAccount(
+    anchor.getProvider().connection,
+    getAssociatedTokenAddressSync(slotMint, user.publicKey)
+  );
+  assert.equal(Number(userAta.amount), 1);
+
+  //Vault ata and vault auth are both closed to the user
+  const vault = await anchor
+    .getProvider()
+    .connection.getAccountInfo(getVault(slotVaultSeed, slotMint));
+  assert.equal(vault, null);
+
+  const vaultAuth = await anchor
+    .getProvider()
+    .connection.getAccountInfo(getVaultAuth(slotVaultSeed));
+  assert.equal(vaultAuth, null);
+});
+
+it("should fail to claim a sol output through the nft path", async () => {
+  const auth = PublicKey.findProgramAddressSync(
+    [Buffer.from("auth"), transmuter.toBytes()],
+    program.programId
+  )[0];
+  const mint = await createMint(
+    anchor.getProvider().connection,
+    user,
+    auth,
+    auth,
+    0
+  );
+  const ata = await getOrCreateAssociatedTokenAccount(
+    anchor.getProvider().connection,
+    user,
+    mint,
+    user.publicKey
+  );
+
+  try {
+    await program.methods
+      .userClaimOutputNft(seed, batchVaultSeed, [])
+      .accounts({
+        creator: creator.publicKey,
+        user: user.publicKey,
+        vaultAuth: getVaultAuth(batchVaultSeed),
+        auth,
+        transmuter,
+        mint,
+        ata: ata.address,
+        metadata: await getMetadata(mint),
+        masterEdition: await getMasterEdition(mint),
+        tokenProgram,
+        associatedTokenProgram,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        sysvarInstructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirmTx);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("UnsupportedOutput"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should fail to abort a vault with a claimed output", async () => {
+  try {
+    await program.methods
+      .userClaimOutputSol(seed, batchVaultSeed)
+      .accounts({
+        creator: creator.publicKey,
+        user: user.publicKey,
+        transmuter,
+        vaultAuth: getVaultAuth(batchVaultSeed),
+        escrow,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc()
+      .then(confirmTx);
+
+    await abortVault(batchVaultSeed, batchMints);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("IsNotClaimable"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
@@ -0,0 +1,75 @@
+import assert from "assert";
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID as tokenProgram, getMint } from "@solana/spl-token";
+import { creator, user } from "./1_init";
+import { VAULT_TTL_SECONDS, seed, transmuter } from "./2_transmuter";
+import { batchMints, batchVaultSeed, getVault, getVaultAuth } from "./3_user";
+import { confirmTx } from "../utils";
+import { program } from "..";
+
+const waitForExpiry = () =>
+  new Promise((resolve) => setTimeout(resolve, (VAULT_TTL_SECONDS + 2) * 1000));
+
+//Burned nft inputs are read as (mint, vault), without a recipient ata
+const refundVault = (mints: PublicKey[]) =>
+  program.methods
+    .vaultRefund(seed, batchVaultSeed, [])
+    .accounts({
+      cranker: creator.publicKey,
+      creator: creator.publicKey,
+      user: user.publicKey,
+      vaultAuth: getVaultAuth(batchVaultSeed),
+      tokenProgram,
+      systemProgram: SystemProgram.programId,
+      coreProgram: null,
+      bubblegumProgram: null,
+      logWrapper: null,
+      compressionProgram: null,
+      transmuter,
+    })
+    .remainingAccounts(
+      mints.flatMap((mint) => [
+        { pubkey: mint, isSigner: false, isWritable: true },
+        {
+          pubkey: getVault(batchVaultSeed, mint),
+          isSigner: false,
+          isWritable: true,
+        },
+      ])
+    )
+    .signers([creator])
+    .rpc()
+    .then(confirmTx);
+
+it("should fail to refund a claimed vault with mismatched inputs", async () => {
+  await waitForExpiry();
+
+  try {
+    await refundVault([batchMints[1], batchMints[0]]);
+  } catch (e) {
+    assert.ok(e instanceof Error);
+    assert.ok(e.message.includes("InvalidInputAccount"));
+    return;
+  }
+  assert.fail("Test should have failed");
+});
+
+it("should burn the inputs of a claimed vault on refund", async () => {
+  await refundVault(batchMints);
+
+  for (const mint of batchMints) {
+    const mintAccount = await getMint(anchor.getProvider().connection, mint);
+    assert.equal(Number(mintAccount.supply), 0);
+
+    const vault = await anchor
+      .getProvider()
+      .connection.getAccountInfo(getVault(batchVaultSeed, mint));
+    assert.equal(vault, null);
+  }
+
+  const vaultAuth = await anchor
+    .getProvider()
+    .connection.getAccountInfo(getVaultAuth(batchVaultSeed));
+  assert.equal(vaultAuth, null);
+});
@@ -18,3 +18,9 @@ import "./case0";
 import "./case1";
 import "./case2";
 import "./case3";
+import "./case4";
+import "./case5";
+import "./case6";
+import "./case7";
+import "./case8";
+import "./case9";
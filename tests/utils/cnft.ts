@@ -0,0 +1,208 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+import {
+  MetadataArgs,
+  PROGRAM_ID as BUBBLEGUM_PROGRAM_ID,
+  computeCreatorHash,
+  computeDataHash,
+  createCreateTreeInstruction,
+  createMintToCollectionV1Instruction,
+  createSetTreeDelegateInstruction,
+} from "@metaplex-foundation/mpl-bubblegum";
+import {
+  ConcurrentMerkleTreeAccount,
+  SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+  SPL_NOOP_PROGRAM_ID,
+  createAllocTreeIx,
+  emptyNode,
+} from "@solana/spl-account-compression";
+import { TOKEN_METADATA_PROGRAM_ID } from "./constants";
+
+export { BUBBLEGUM_PROGRAM_ID };
+
+// Small trees, every proof is passed in full
+const TREE_DEPTH = 3;
+const TREE_BUFFER_SIZE = 8;
+
+export const getTreeConfig = (merkleTree: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [merkleTree.toBuffer()],
+    BUBBLEGUM_PROGRAM_ID
+  )[0];
+
+export const getBubblegumSigner = () =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("collection_cpi")],
+    BUBBLEGUM_PROGRAM_ID
+  )[0];
+
+//Trees minted by the program are delegated to its auth PDA
+export const createTree = async (
+  treeCreator: Keypair,
+  treeDelegate: PublicKey | null = null
+) => {
+  const merkleTree = Keypair.generate();
+  const connection = anchor.getProvider().connection;
+
+  const tx = new Transaction().add(
+    await createAllocTreeIx(
+      connection,
+      merkleTree.publicKey,
+      treeCreator.publicKey,
+      { maxDepth: TREE_DEPTH, maxBufferSize: TREE_BUFFER_SIZE },
+      0
+    ),
+    createCreateTreeInstruction(
+      {
+        treeAuthority: getTreeConfig(merkleTree.publicKey),
+        merkleTree: merkleTree.publicKey,
+        payer: treeCreator.publicKey,
+        treeCreator: treeCreator.publicKey,
+        logWrapper: SPL_NOOP_PROGRAM_ID,
+        compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+      },
+      {
+        maxDepth: TREE_DEPTH,
+        maxBufferSize: TREE_BUFFER_SIZE,
+        public: false,
+      }
+    )
+  );
+
+  if (treeDelegate !== null) {
+    tx.add(
+      createSetTreeDelegateInstruction({
+        treeAuthority: getTreeConfig(merkleTree.publicKey),
+        treeCreator: treeCreator.publicKey,
+        newTreeDelegate: treeDelegate,
+        merkleTree: merkleTree.publicKey,
+      })
+    );
+  }
+
+  await anchor.web3.sendAndConfirmTransaction(
+    connection,
+    tx,
+    [treeCreator, merkleTree],
+    { commitment: "confirmed" }
+  );
+
+  return merkleTree.publicKey;
+};
+
+//The tree creator is also the collection update authority, pass the metadata
+//as it ends up in the leaf, Bubblegum verifies the collection itself
+export const mintCnftToCollection = async (
+  treeCreator: Keypair,
+  leafOwner: PublicKey,
+  merkleTree: PublicKey,
+  collection: { mint: PublicKey; metadata: PublicKey; edition: PublicKey },
+  metadata: MetadataArgs
+) => {
+  const ix = createMintToCollectionV1Instruction(
+    {
+      treeAuthority: getTreeConfig(merkleTree),
+      leafOwner,
+      leafDelegate: leafOwner,
+      merkleTree,
+      payer: treeCreator.publicKey,
+      treeDelegate: treeCreator.publicKey,
+      collectionAuthority: treeCreator.publicKey,
+      collectionAuthorityRecordPda: BUBBLEGUM_PROGRAM_ID,
+      collectionMint: collection.mint,
+      collectionMetadata: collection.metadata,
+      editionAccount: collection.edition,
+      bubblegumSigner: getBubblegumSigner(),
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+    },
+    {
+      metadataArgs: {
+        ...metadata,
+        collection: { ...metadata.collection, verified: false },
+      },
+    }
+  );
+
+  await anchor.web3.sendAndConfirmTransaction(
+    anchor.getProvider().connection,
+    new Transaction().add(ix),
+    [treeCreator],
+    { commitment: "confirmed" }
+  );
+};
+
+export const getAssetId = (merkleTree: PublicKey, nonce: number) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("asset"),
+      merkleTree.toBuffer(),
+      new anchor.BN(nonce).toArrayLike(Buffer, "le", 8),
+    ],
+    BUBBLEGUM_PROGRAM_ID
+  )[0];
+
+//Only valid for the first leaf of a tree holding a single leaf
+export const getFirstLeafProof = (): AccountMeta[] =>
+  [...Array(TREE_DEPTH).keys()].map((level) => ({
+    pubkey: new PublicKey(emptyNode(level)),
+    isSigner: false,
+    isWritable: false,
+  }));
+
+export const getLeafInfo = async (
+  merkleTree: PublicKey,
+  metadata: MetadataArgs,
+  nonce: number
+) => {
+  const tree = await ConcurrentMerkleTreeAccount.fromAccountAddress(
+    anchor.getProvider().connection,
+    merkleTree,
+    "confirmed"
+  );
+
+  return {
+    root: Array.from(tree.getCurrentRoot()),
+    dataHash: Array.from(computeDataHash(metadata)),
+    creatorHash: Array.from(computeCreatorHash(metadata.creators)),
+    nonce: new anchor.BN(nonce),
+    index: nonce,
+  };
+};
+
+export const toMetadataJson = (metadata: MetadataArgs) =>
+  JSON.stringify({
+    name: metadata.name,
+    symbol: metadata.symbol,
+    uri: metadata.uri,
+    seller_fee_basis_points: metadata.sellerFeeBasisPoints,
+    primary_sale_happened: metadata.primarySaleHappened,
+    is_mutable: metadata.isMutable,
+    edition_nonce: metadata.editionNonce,
+    token_standard: "NonFungible",
+    collection: metadata.collection && {
+      verified: metadata.collection.verified,
+      key: metadata.collection.key.toBase58(),
+    },
+    uses: null,
+    token_program_version: "Original",
+    creators: metadata.creators.map((creator) => ({
+      address: creator.address.toBase58(),
+      verified: creator.verified,
+      share: creator.share,
+    })),
+  });
+
+export const cnftPrograms = {
+  logWrapper: SPL_NOOP_PROGRAM_ID,
+  compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+  bubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+  systemProgram: SystemProgram.programId,
+};
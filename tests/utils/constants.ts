@@ -10,3 +10,10 @@ export const TOKEN_DECIMALS = 1_000_000;
 export const WBA = new PublicKey(
   "3LSY4UTEFt7V7eGsiaAUDzn3iKAJFBPkYseXpdECFknF"
 );
+
+export const MPL_CORE_PROGRAM_ID = new PublicKey(
+  "CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d"
+);
+
+//Paid to whoever resolves an input, matches the program constant
+export const RESOLVE_TIP_LAMPORTS = 5000;
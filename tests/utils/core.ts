@@ -0,0 +1,118 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import { MPL_CORE_PROGRAM_ID } from "./constants";
+
+// Metaplex Core instruction discriminators (see mpl-core `MplAssetInstruction`)
+const CREATE_V1 = 0;
+const CREATE_COLLECTION_V1 = 1;
+
+const serializeString = (value: string) => {
+  const bytes = Buffer.from(value, "utf8");
+  const length = Buffer.alloc(4);
+  length.writeUInt32LE(bytes.length);
+  return Buffer.concat([length, bytes]);
+};
+
+//Optional Core accounts are passed as the Core program id when missing
+const optionalMeta = (
+  pubkey: PublicKey | null,
+  isSigner = false,
+  isWritable = false
+) => ({
+  pubkey: pubkey ?? MPL_CORE_PROGRAM_ID,
+  isSigner: pubkey !== null && isSigner,
+  isWritable: pubkey !== null && isWritable,
+});
+
+export const createCoreCollection = async (
+  updateAuthority: Keypair,
+  name: string,
+  uri: string
+) => {
+  const collection = Keypair.generate();
+
+  // plugins: None
+  const data = Buffer.concat([
+    Buffer.from([CREATE_COLLECTION_V1]),
+    serializeString(name),
+    serializeString(uri),
+    Buffer.from([0]),
+  ]);
+
+  const ix = new TransactionInstruction({
+    programId: MPL_CORE_PROGRAM_ID,
+    keys: [
+      { pubkey: collection.publicKey, isSigner: true, isWritable: true },
+      optionalMeta(updateAuthority.publicKey),
+      { pubkey: updateAuthority.publicKey, isSigner: true, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    data,
+  });
+
+  await anchor.web3.sendAndConfirmTransaction(
+    anchor.getProvider().connection,
+    new Transaction().add(ix),
+    [updateAuthority, collection],
+    { commitment: "confirmed" }
+  );
+
+  return collection.publicKey;
+};
+
+//The collection update authority signs and pays for assets minted into it
+export const createCoreAsset = async (
+  authority: Keypair,
+  owner: PublicKey,
+  collection: PublicKey | null,
+  name: string,
+  uri: string
+) => {
+  const asset = Keypair.generate();
+
+  // data_state: AccountState, plugins: None
+  const data = Buffer.concat([
+    Buffer.from([CREATE_V1, 0]),
+    serializeString(name),
+    serializeString(uri),
+    Buffer.from([0]),
+  ]);
+
+  const ix = new TransactionInstruction({
+    programId: MPL_CORE_PROGRAM_ID,
+    keys: [
+      { pubkey: asset.publicKey, isSigner: true, isWritable: true },
+      optionalMeta(collection, false, true),
+      optionalMeta(authority.publicKey, true),
+      { pubkey: authority.publicKey, isSigner: true, isWritable: true },
+      optionalMeta(owner),
+      optionalMeta(null),
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      optionalMeta(null),
+    ],
+    data,
+  });
+
+  await anchor.web3.sendAndConfirmTransaction(
+    anchor.getProvider().connection,
+    new Transaction().add(ix),
+    [authority, asset],
+    { commitment: "confirmed" }
+  );
+
+  return asset.publicKey;
+};
+
+//The owner follows the one byte account key
+export const getCoreOwner = async (asset: PublicKey) => {
+  const account = await anchor
+    .getProvider()
+    .connection.getAccountInfo(asset, "confirmed");
+  return account === null ? null : new PublicKey(account.data.subarray(1, 33));
+};
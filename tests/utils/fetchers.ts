@@ -1,6 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Transmuter } from "../../target/types/transmuter";
 import { PublicKey, Connection } from "@solana/web3.js";
+import { Metaplex } from "@metaplex-foundation/js";
 
 const TOKEN_METADATA_PROGRAM_ID = new anchor.web3.PublicKey(
   "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
@@ -106,6 +107,21 @@ export const getvaultAuthStructs = async (
   ]);
 };
 
+// Input uris are only stored as hashes, rules need them back in slot order
+export const getInputUris = async (
+  metaplex: Metaplex,
+  handledInputs: (PublicKey | null)[]
+): Promise<string[]> => {
+  return Promise.all(
+    handledInputs.map(async (mintAddress) => {
+      const nft = await metaplex
+        .nfts()
+        .findByMint({ mintAddress, loadJsonMetadata: false });
+      return nft.uri;
+    })
+  );
+};
+
 // Publickeys
 export const getMetadata = async (
   mint: anchor.web3.PublicKey
@@ -134,6 +150,23 @@ export const getMasterEdition = async (
   )[0];
 };
 
+//One edition marker covers 248 prints of a master edition
+export const getEditionMarker = async (
+  mint: anchor.web3.PublicKey,
+  edition: number
+): Promise<anchor.web3.PublicKey> => {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+      Buffer.from("edition"),
+      Buffer.from(Math.floor(edition / 248).toString()),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+};
+
 export const getProgramAuthority = async (
   c: Connection,
   programId: PublicKey
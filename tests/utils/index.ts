@@ -2,3 +2,5 @@ export * from "./helpers";
 export * from "./constants";
 export * from "./types";
 export * from "./fetchers";
+export * from "./core";
+export * from "./cnft";
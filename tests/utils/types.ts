@@ -15,6 +15,10 @@ export type OutputInfo = {
   uri?: String;
   mint_info?: MintInfo;
   mint?: String;
+  merkle_tree?: String;
+  pool?: String;
+  max_supply?: number;
+  escrow?: String;
 };
 
 export type TraitInfo = {